@@ -1,13 +1,17 @@
 use crate::{CsvError, Reader, Result, Row, Writer};
 use std::{
-    collections::HashMap,
+    collections::{hash_map::DefaultHasher, HashMap, HashSet},
     fmt::Display,
     fs::File,
-    path::Path,
+    hash::{Hash, Hasher},
+    path::{Path, PathBuf},
     slice::{Iter, IterMut},
     str::FromStr,
 };
 
+/// Default maximum characters per cell used by [`Document::print_table`].
+pub const DEFAULT_MAX_CELL_WIDTH: usize = 40;
+
 /// Simple document structure. This is merely an in-memory wrapper around a set of CSV Rows.
 /// It offers functions to retrieve and write data as well as a way to serialize to a file.
 ///
@@ -30,15 +34,21 @@ use std::{
 ///     "jeng@mail.com",
 ///     "Marktown High School"
 /// ]);
-/// doc.write_to_file("malist.csv")
+/// let path = std::env::temp_dir().join(format!("malist_{}.csv", std::process::id()));
+/// doc.write_to_file(&path)
 ///     .expect("Error writing to file");
+/// # std::fs::remove_file(&path).unwrap();
 /// ```
 ///
-#[derive(Debug, Clone, PartialEq, Default)]
+#[derive(Clone, Default)]
 pub struct Document {
     headers: Option<Row>,
     rows: Vec<Row>,
     header_indexes: HashMap<String, usize>,
+    load_errors: usize,
+    missing_as_empty: bool,
+    /// Positions of the rows sharing each row hash, see [`Document::build_row_index`].
+    row_index: Option<HashMap<u64, Vec<usize>>>,
 }
 
 impl Document {
@@ -56,7 +66,58 @@ impl Document {
             headers: Some(Row::from(headers)),
             rows: Vec::new(),
             header_indexes,
+            load_errors: 0,
+            missing_as_empty: false,
+            row_index: None,
+        }
+    }
+
+    /// Create an empty document with an optional header row, names are decoded lossily.
+    pub(crate) fn with_header_row(headers: Option<Row>) -> Self {
+        let mut header_indexes = HashMap::new();
+        if let Some(header) = &headers {
+            for (index, value) in header.iter().enumerate() {
+                header_indexes.insert(String::from_utf8_lossy(value.as_bytes()).to_string(), index);
+            }
+        }
+        Document {
+            headers,
+            header_indexes,
+            ..Document::default()
+        }
+    }
+
+    /// Create a new document with the given headers, holding one row per item.
+    ///
+    /// Items only need to convert into a [`Row`], so typed records can implement `From<T> for Row`.
+    ///
+    /// # Arguments
+    /// `headers`   a slice of string literals containing the headers for this document.
+    /// `items`     typed records to turn into rows, in order.
+    pub fn from_iter_typed<I, T>(headers: &[&str], items: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<Row>,
+    {
+        let mut document = Document::with_headers(headers);
+        document.rows = items.into_iter().map(Into::into).collect();
+        document
+    }
+
+    /// Create a new document with the given headers from rows of owned strings.
+    ///
+    /// # Arguments
+    /// `headers`   a slice of string literals containing the headers for this document.
+    /// `data`      one vector of fields per row, in order.
+    ///
+    /// # Errors
+    /// [`CsvError::RecordError`] if a row's field count differs from the header count.
+    pub fn from_rows(headers: &[&str], data: Vec<Vec<String>>) -> Result<Self> {
+        let mut document = Document::with_headers(headers);
+        for fields in data {
+            document.add_row_checked(fields)?;
         }
+        Ok(document)
     }
 
     /// Create a document for a given path.
@@ -84,6 +145,97 @@ impl Document {
         Document::try_from(reader)
     }
 
+    /// Create a document for a given path, parsing the records across threads.
+    ///
+    /// The file is read in memory and split on record boundaries, quoted line breaks
+    /// included. The header is parsed first, then the chunks are parsed in parallel and
+    /// their rows concatenated in file order, so the result matches [`Document::from_path`].
+    ///
+    /// # Arguments
+    /// `path` path/string to file to be read.
+    ///
+    /// # Errors
+    /// If the file cannot be accessed or its header cannot be read.
+    #[cfg(feature = "rayon")]
+    pub fn from_path_parallel(path: impl AsRef<Path>) -> Result<Self> {
+        use crate::DEFAULT_DELIM;
+        use rayon::prelude::*;
+
+        let data = std::fs::read(path)?;
+        // A few chunks per thread keeps the threads busy when some chunks parse slower
+        let chunks = crate::reader::split_records(
+            &data,
+            DEFAULT_DELIM as u8,
+            rayon::current_num_threads() * 4,
+        );
+        let header = chunks.first().copied().unwrap_or_default();
+        let mut document = Document::try_from(
+            Reader::builder()
+                .with_reader(header)
+                .with_delimiter(DEFAULT_DELIM)
+                .with_header(true)
+                .build()?,
+        )?;
+
+        let parsed: Vec<Vec<Row>> = chunks
+            .get(1..)
+            .unwrap_or_default()
+            .par_iter()
+            .map(|chunk| {
+                Reader::builder()
+                    .with_reader(*chunk)
+                    .with_delimiter(DEFAULT_DELIM)
+                    .build()
+                    .map(|reader| reader.entries().collect())
+            })
+            .collect::<Result<_>>()?;
+        document.rows = parsed.into_iter().flatten().collect();
+        Ok(document)
+    }
+
+    /// Create a document for a given path, skipping any malformed records.
+    ///
+    /// A record is considered malformed when its field count differs from the header's
+    /// or when any of its fields is not valid UTF-8. Reading carries on with the next
+    /// record after a malformed one, and every record that could not be loaded is
+    /// counted and can be queried through [`Document::load_errors`].
+    ///
+    /// # Arguments
+    /// `path` path/string to file to be read.
+    ///
+    /// # Errors
+    /// Only if the file cannot be accessed or does not exist.
+    pub fn from_path_lossy(path: impl AsRef<Path>) -> Result<Self> {
+        let reader = Reader::from_path(path)?;
+        let mut document = Document::with_header_row(reader.headers());
+        for entry in reader.entries().resync() {
+            match entry {
+                Ok(row) => document.rows.push(row),
+                Err(_) => document.load_errors += 1,
+            }
+        }
+        Ok(document)
+    }
+
+    /// Sets whether header columns missing from short rows read as empty values.
+    ///
+    /// When enabled, accessing a column a ragged row is too short to contain behaves as if
+    /// the field were empty, like spreadsheets do, instead of failing with [`CsvError::NotAField`].
+    ///
+    /// # Arguments
+    /// `enabled` boolean whether missing trailing columns read as empty.
+    pub fn with_missing_as_empty(mut self, enabled: bool) -> Self {
+        self.missing_as_empty = enabled;
+        self
+    }
+
+    /// Get the number of records skipped while loading the document.
+    ///
+    /// Only [`Document::from_path_lossy`] skips records, every other constructor reports zero.
+    pub fn load_errors(&self) -> usize {
+        self.load_errors
+    }
+
     /// Create an empty document without headers
     pub fn empty() -> Self {
         Document::default()
@@ -91,11 +243,32 @@ impl Document {
 
     /// Inserts a new row to the document.
     ///
+    /// The row length is not validated, see [`Document::add_row_checked`].
+    ///
     /// # Arguments
     /// `row` Row being inserted.
     pub fn add_row(&mut self, row: Row) {
-        // TODO: Validate row length
         self.rows.push(row);
+        self.index_row(self.rows.len() - 1);
+    }
+
+    /// Inserts a new row to the document, making sure it is as wide as the header.
+    ///
+    /// # Arguments
+    /// `row` Row being inserted.
+    ///
+    /// # Errors
+    /// [`CsvError::RecordError`] if the document has headers and the row's field count differs.
+    pub fn add_row_checked<T: Into<Row>>(&mut self, row: T) -> Result<()> {
+        let row = row.into();
+        if let Some(headers) = &self.headers {
+            if headers.count() != row.count() {
+                return Err(CsvError::RecordError);
+            }
+        }
+        self.rows.push(row);
+        self.index_row(self.rows.len() - 1);
+        Ok(())
     }
 
     /// Inserts multiple rows to the document.
@@ -103,7 +276,110 @@ impl Document {
     /// # Arguments
     /// `row` Row being inserted.
     pub fn add_all(&mut self, rows: &[Row]) {
+        let start = self.rows.len();
         self.rows.extend_from_slice(rows);
+        for position in start..self.rows.len() {
+            self.index_row(position);
+        }
+    }
+
+    /// Builds an index of row hashes so [`Document::contains_row`] no longer scans every row.
+    ///
+    /// The index is opt-in since it takes extra memory. Methods changing rows keep it
+    /// up to date, except [`Document::rows_mut`] which drops it, call this again afterwards.
+    pub fn build_row_index(&mut self) {
+        self.row_index = Some(HashMap::new());
+        for position in 0..self.rows.len() {
+            self.index_row(position);
+        }
+    }
+
+    /// Check whether the document holds a row with the same fields, delimiters are ignored.
+    ///
+    /// Uses the index from [`Document::build_row_index`] when built, otherwise scans every row.
+    ///
+    /// # Arguments
+    /// `row` row being searched.
+    pub fn contains_row(&self, row: &Row) -> bool {
+        match &self.row_index {
+            // Rows sharing a hash are compared, so hash collisions cannot give false positives
+            Some(index) => index.get(&row_hash(row)).is_some_and(|positions| {
+                positions
+                    .iter()
+                    .any(|position| self.rows[*position].content_eq(row))
+            }),
+            None => self.rows.iter().any(|existing| existing.content_eq(row)),
+        }
+    }
+
+    /// Adds the row at the given position to the row index, if one was built.
+    fn index_row(&mut self, position: usize) {
+        if let Some(index) = &mut self.row_index {
+            let hash = row_hash(&self.rows[position]);
+            index.entry(hash).or_default().push(position);
+        }
+    }
+
+    /// Removes the row at the given position from the row index, if one was built.
+    fn unindex_row(&mut self, position: usize) {
+        if let Some(index) = &mut self.row_index {
+            let hash = row_hash(&self.rows[position]);
+            if let Some(positions) = index.get_mut(&hash) {
+                positions.retain(|existing| *existing != position);
+                if positions.is_empty() {
+                    index.remove(&hash);
+                }
+            }
+        }
+    }
+
+    /// Rebuilds the row index after rows changed, if one was built.
+    fn refresh_row_index(&mut self) {
+        if self.row_index.is_some() {
+            self.build_row_index();
+        }
+    }
+
+    /// Appends the rows of another document, matching its columns to this document's by header name.
+    ///
+    /// Each appended row is reordered to this document's column order, and columns
+    /// `other` does not have are filled with empty fields.
+    ///
+    /// # Arguments
+    /// `other` document whose rows are appended.
+    ///
+    /// # Errors
+    /// [`CsvError::InvalidColumn`] if `other` has a column this document does not have.
+    /// [`CsvError::Generic`] if either document has no headers to align on.
+    pub fn append_aligned(&mut self, other: &Document) -> Result<()> {
+        let (Some(headers), Some(other_headers)) = (&self.headers, &other.headers) else {
+            return Err(CsvError::Generic(
+                "Cannot align documents without headers.".to_string(),
+            ));
+        };
+
+        // For every column of this document, the index of the matching column in `other`
+        let mut sources = vec![None; headers.count()];
+        for (other_index, name) in other_headers.iter().enumerate() {
+            let name = name.to_string()?;
+            match self.header_indexes.get(&name) {
+                Some(index) => sources[*index] = Some(other_index),
+                None => return Err(CsvError::InvalidColumn(name)),
+            }
+        }
+
+        for row in &other.rows {
+            let mut aligned = Row::with_capacity(row.inner.len());
+            for source in &sources {
+                match source {
+                    Some(index) => aligned.add_field_from(row, *index),
+                    None => aligned.add_bytes(&[]),
+                }
+            }
+            self.rows.push(aligned);
+            self.index_row(self.rows.len() - 1);
+        }
+        Ok(())
     }
 
     /// Remove anexisting row from a document.
@@ -113,7 +389,57 @@ impl Document {
     pub fn remove_row(&mut self, row: usize) {
         if row < self.rows.len() {
             self.rows.remove(row);
+            self.refresh_row_index();
+        }
+    }
+
+    /// Remove rows that are exact duplicates of an earlier row, keeping the first occurrence.
+    pub fn dedup(&mut self) {
+        let mut seen = HashSet::new();
+        self.rows
+            .retain(|row| seen.insert((row.inner.clone(), row.ranges.clone(), row.nulls.clone())));
+        self.refresh_row_index();
+    }
+
+    /// Keep only the first row for every distinct value of the given column.
+    ///
+    /// # Arguments
+    /// `col_name` name of the column used to detect duplicates.
+    ///
+    /// # Errors
+    /// If the given column name does not exist in the document.
+    pub fn dedup_by_column(&mut self, col_name: &str) -> Result<()> {
+        let column = *self
+            .header_indexes
+            .get(col_name)
+            .ok_or_else(|| CsvError::InvalidColumn(col_name.to_string()))?;
+        let mut seen = HashSet::new();
+        self.rows
+            .retain(|row| seen.insert(row.get_range(column).map(<[u8]>::to_vec)));
+        self.refresh_row_index();
+        Ok(())
+    }
+
+    /// Get every entry whose value in the given column appears more than once in the document.
+    ///
+    /// # Arguments
+    /// `col_name` name of the column used to detect duplicates.
+    ///
+    /// # Errors
+    /// If the given column name does not exist in the document.
+    pub fn duplicates_by_column(&self, col_name: &str) -> Result<Vec<DocEntry<'_>>> {
+        let column = *self
+            .header_indexes
+            .get(col_name)
+            .ok_or_else(|| CsvError::InvalidColumn(col_name.to_string()))?;
+        let mut occurrences: HashMap<Option<&[u8]>, usize> = HashMap::new();
+        for row in &self.rows {
+            *occurrences.entry(row.get_range(column)).or_default() += 1;
         }
+        Ok(self
+            .rows()
+            .filter(|entry| occurrences[&entry.row.get_range(column)] > 1)
+            .collect())
     }
 
     /// Get the given column for every row in the document.
@@ -144,6 +470,116 @@ impl Document {
         }
     }
 
+    /// Get several columns for every row in the document, all parsed into the same type.
+    ///
+    /// # Arguments
+    /// `names` names of the columns being searched.
+    ///
+    /// # Returns
+    /// One vector of values per requested column, in the order requested.
+    ///
+    /// # Errors
+    /// [`CsvError::InvalidColumn`] if one of the columns does not exist.
+    /// [`CsvError::ColumnConversionError`] naming the column and row of the first value
+    /// that cannot be parsed into the type T.
+    pub fn columns_as<T: std::str::FromStr>(&self, names: &[&str]) -> Result<Vec<Vec<T>>> {
+        names
+            .iter()
+            .map(|name| {
+                let column = *self
+                    .header_indexes
+                    .get(*name)
+                    .ok_or_else(|| CsvError::InvalidColumn(name.to_string()))?;
+                let missing_as_empty = self.missing_as_empty && column < self.column_count();
+                self.rows
+                    .iter()
+                    .enumerate()
+                    .map(|(row_index, row)| {
+                        get_cell(row, column, missing_as_empty).map_err(|e| match e {
+                            CsvError::ConversionError(_, type_name) => {
+                                CsvError::ColumnConversionError(
+                                    name.to_string(),
+                                    row_index,
+                                    type_name,
+                                )
+                            }
+                            e => e,
+                        })
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Get two columns for every row in the document, parsed into a tuple per row.
+    ///
+    /// # Arguments
+    /// `col_a` name of the column parsed into the first value.
+    /// `col_b` name of the column parsed into the second value.
+    ///
+    /// # Errors
+    /// [`CsvError::InvalidColumn`] if one of the columns does not exist.
+    /// If a value cannot properly be parsed into its type.
+    pub fn rows_as2<A, B>(&self, col_a: &str, col_b: &str) -> Result<Vec<(A, B)>>
+    where
+        A: std::str::FromStr,
+        B: std::str::FromStr,
+    {
+        self.check_columns(&[col_a, col_b])?;
+        self.rows()
+            .map(|entry| Ok((entry.get_value(col_a)?, entry.get_value(col_b)?)))
+            .collect()
+    }
+
+    /// Get three columns for every row in the document, parsed into a tuple per row.
+    ///
+    /// # Arguments
+    /// `col_a` name of the column parsed into the first value.
+    /// `col_b` name of the column parsed into the second value.
+    /// `col_c` name of the column parsed into the third value.
+    ///
+    /// # Errors
+    /// [`CsvError::InvalidColumn`] if one of the columns does not exist.
+    /// If a value cannot properly be parsed into its type.
+    pub fn rows_as3<A, B, C>(&self, col_a: &str, col_b: &str, col_c: &str) -> Result<Vec<(A, B, C)>>
+    where
+        A: std::str::FromStr,
+        B: std::str::FromStr,
+        C: std::str::FromStr,
+    {
+        self.check_columns(&[col_a, col_b, col_c])?;
+        self.rows()
+            .map(|entry| {
+                Ok((
+                    entry.get_value(col_a)?,
+                    entry.get_value(col_b)?,
+                    entry.get_value(col_c)?,
+                ))
+            })
+            .collect()
+    }
+
+    /// Fails with [`CsvError::InvalidColumn`] naming the first column that does not exist.
+    fn check_columns(&self, names: &[&str]) -> Result<()> {
+        match names.iter().find(|name| !self.is_valid_column(name)) {
+            Some(name) => Err(CsvError::InvalidColumn(name.to_string())),
+            None => Ok(()),
+        }
+    }
+
+    /// Get the given column for every row in the document, matching the column name case-insensitively.
+    ///
+    /// # Arguments
+    /// `col_name` name of the column being searched, in any casing.
+    ///
+    /// # Errors
+    /// If no column matches the given name, if several columns match it
+    /// or if the data cannot properly be parsed into the type T.
+    pub fn get_column_ci<T: std::str::FromStr>(&self, col_name: &str) -> Result<Vec<T>> {
+        let index = self.column_index_ci(col_name)?;
+        self.get_column_by_index(index)
+    }
+
     /// Get the given column for every row in the document by using the column index.
     ///
     /// # Arguments
@@ -153,9 +589,10 @@ impl Document {
     /// If the given column index does not exist in the row
     /// or if the data cannot properly be parsed into the type T.
     pub fn get_column_by_index<T: std::str::FromStr>(&self, column: usize) -> Result<Vec<T>> {
+        let missing_as_empty = self.missing_as_empty && column < self.column_count();
         let mut result_vec = Vec::new();
         for row in &self.rows {
-            result_vec.push(row.get(column)?);
+            result_vec.push(get_cell(row, column, missing_as_empty)?);
         }
         Ok(result_vec)
     }
@@ -171,7 +608,8 @@ impl Document {
     /// or if the data cannot properly be parsed into the type T.
     pub fn get_value_at<T: std::str::FromStr>(&self, row: usize, column: usize) -> Result<T> {
         if let Some(row) = self.rows.get(row) {
-            row.get::<T>(column)
+            let missing_as_empty = self.missing_as_empty && column < self.column_count();
+            get_cell(row, column, missing_as_empty)
         } else {
             Err(CsvError::InvalidRow(row))
         }
@@ -240,22 +678,204 @@ impl Document {
     pub fn rows<'a>(&'a self) -> DocIter<'a> {
         DocIter {
             header_indexes: &self.header_indexes,
+            missing_as_empty: self.missing_as_empty,
             iter: self.rows.iter(),
         }
     }
+    /// Get an iterator to all the rows in the document along with their row index.
+    pub fn enumerate_rows(&self) -> impl Iterator<Item = (usize, DocEntry<'_>)> {
+        self.rows().enumerate()
+    }
+
+    /// Filter and transform the rows in a single pass, keeping the values `f` returns as `Some`.
+    ///
+    /// # Arguments
+    /// `f` function turning a row into a value, or `None` to leave it out.
+    pub fn filter_map_rows<T, F>(&self, f: F) -> Vec<T>
+    where
+        F: Fn(&DocEntry) -> Option<T>,
+    {
+        self.rows().filter_map(|entry| f(&entry)).collect()
+    }
+
     /// Get a mutable iterator to all the rows in the document
     pub fn rows_mut<'a>(&'a mut self) -> DocIterMut<'a> {
+        // Rows may change through the iterator without the index noticing
+        self.row_index = None;
         DocIterMut {
             header_indexes: &self.header_indexes,
+            missing_as_empty: self.missing_as_empty,
             iter: self.rows.iter_mut(),
         }
     }
 
+    /// Apply a transform to the rows matching a predicate, leaving the other rows untouched.
+    ///
+    /// # Arguments
+    /// `predicate` decides whether a row is transformed.
+    /// `f` transform applied to every matching row.
+    pub fn apply_if<P, F>(&mut self, predicate: P, mut f: F)
+    where
+        P: Fn(&DocEntry) -> bool,
+        F: FnMut(&mut DocEntryMut),
+    {
+        for row in &mut self.rows {
+            let entry = DocEntry {
+                row,
+                header_indexes: &self.header_indexes,
+                missing_as_empty: self.missing_as_empty,
+            };
+            if predicate(&entry) {
+                f(&mut DocEntryMut {
+                    row,
+                    header_indexes: &self.header_indexes,
+                    missing_as_empty: self.missing_as_empty,
+                });
+            }
+        }
+        self.refresh_row_index();
+    }
+
+    /// Decode every row in the document, splitting successes from failures in a single pass.
+    ///
+    /// Rows are decoded through `T`'s `TryFrom<DocEntry>` implementation. Failures are
+    /// returned alongside the index of the row that produced them.
+    ///
+    /// # Example
+    /// ```rust
+    /// use csvlib::{doc::DocEntry, CsvError, Document};
+    ///
+    /// struct Student {
+    ///     age: u32,
+    /// }
+    ///
+    /// impl TryFrom<DocEntry<'_>> for Student {
+    ///     type Error = CsvError;
+    ///     fn try_from(entry: DocEntry) -> Result<Self, Self::Error> {
+    ///         Ok(Student { age: entry.get_value("Age")? })
+    ///     }
+    /// }
+    ///
+    /// let mut doc = Document::with_headers(&["Name", "Age"]);
+    /// doc.add_row(csvlib::csv!["Mike", 15]);
+    /// doc.add_row(csvlib::csv!["Jenny", "sixteen"]);
+    ///
+    /// let (students, errors) = doc.partition_decoded::<Student>();
+    /// assert_eq!(students.len(), 1);
+    /// assert_eq!(errors[0].0, 1);
+    /// ```
+    pub fn partition_decoded<'a, T>(&'a self) -> (Vec<T>, Vec<(usize, CsvError)>)
+    where
+        T: TryFrom<DocEntry<'a>, Error = CsvError>,
+    {
+        let mut decoded = Vec::new();
+        let mut errors = Vec::new();
+        for (index, entry) in self.rows().enumerate() {
+            match T::try_from(entry) {
+                Ok(value) => decoded.push(value),
+                Err(e) => errors.push((index, e)),
+            }
+        }
+        (decoded, errors)
+    }
+
+    /// Parallel version of [`Document::partition_decoded`].
+    ///
+    /// Rows are decoded across threads and merged back in row order, so the
+    /// output is identical to the sequential version.
+    #[cfg(feature = "rayon")]
+    pub fn par_partition_decoded<'a, T>(&'a self) -> (Vec<T>, Vec<(usize, CsvError)>)
+    where
+        T: TryFrom<DocEntry<'a>, Error = CsvError> + Send,
+    {
+        use rayon::prelude::*;
+
+        let results: Vec<Result<T>> = self
+            .rows
+            .par_iter()
+            .map(|row| {
+                T::try_from(DocEntry {
+                    row,
+                    header_indexes: &self.header_indexes,
+                    missing_as_empty: self.missing_as_empty,
+                })
+            })
+            .collect();
+
+        let mut decoded = Vec::new();
+        let mut errors = Vec::new();
+        for (index, result) in results.into_iter().enumerate() {
+            match result {
+                Ok(value) => decoded.push(value),
+                Err(e) => errors.push((index, e)),
+            }
+        }
+        (decoded, errors)
+    }
+
+    /// Decodes every row across threads through `T`'s `TryFrom<DocEntry>` implementation.
+    ///
+    /// Decoded values keep the row order. Unlike [`Document::par_partition_decoded`],
+    /// only the first failure is reported.
+    ///
+    /// # Errors
+    /// The error of the first row, in document order, that could not be decoded.
+    #[cfg(feature = "rayon")]
+    pub fn rows_decoded_par<'a, T>(&'a self) -> Result<Vec<T>>
+    where
+        T: TryFrom<DocEntry<'a>, Error = CsvError> + Send,
+    {
+        use rayon::prelude::*;
+
+        self.rows
+            .par_iter()
+            .map(|row| {
+                T::try_from(DocEntry {
+                    row,
+                    header_indexes: &self.header_indexes,
+                    missing_as_empty: self.missing_as_empty,
+                })
+            })
+            .collect::<Vec<_>>()
+            .into_iter()
+            .collect()
+    }
+
     /// Get the count of all rows in the document
     pub fn count(&self) -> usize {
         self.rows.len()
     }
 
+    /// Get the count of all rows in the document, same as [`Document::count`].
+    pub fn row_count(&self) -> usize {
+        self.count()
+    }
+
+    /// Get the count of columns in the document's header, zero without a header.
+    pub fn column_count(&self) -> usize {
+        self.headers.as_ref().map_or(0, Row::count)
+    }
+
+    /// Get the `(rows, columns)` dimensions of the document.
+    pub fn shape(&self) -> (usize, usize) {
+        (self.row_count(), self.column_count())
+    }
+
+    /// Get every cell of the document, header excluded, as strings.
+    ///
+    /// Handy as a plain assertion target in tests. Invalid UTF-8 is replaced lossily.
+    pub fn to_string_matrix(&self) -> Vec<Vec<String>> {
+        self.rows.iter().map(row_to_strings).collect()
+    }
+
+    /// Get the headers of the document as strings, empty when the document has none.
+    pub fn headers_vec(&self) -> Vec<String> {
+        self.headers
+            .as_ref()
+            .map(row_to_strings)
+            .unwrap_or_default()
+    }
+
     /// Check whether the given row exists in the document
     ///
     /// # Arguments
@@ -280,6 +900,29 @@ impl Document {
         self.header_indexes.contains_key(column)
     }
 
+    /// Check whether the given column name exists in the document, ignoring casing.
+    ///
+    /// Names matching several columns are not considered valid.
+    ///
+    /// # Arguments
+    /// `column`    column name to search
+    pub fn is_valid_column_ci(&self, column: &str) -> bool {
+        self.column_index_ci(column).is_ok()
+    }
+
+    /// Find the index of the single column whose name matches case-insensitively.
+    fn column_index_ci(&self, col_name: &str) -> Result<usize> {
+        let mut matches = self
+            .header_indexes
+            .iter()
+            .filter(|(name, _)| name.to_lowercase() == col_name.to_lowercase());
+        match (matches.next(), matches.next()) {
+            (Some((_, index)), None) => Ok(*index),
+            (Some(_), Some(_)) => Err(CsvError::AmbiguousColumn(col_name.to_string())),
+            _ => Err(CsvError::InvalidColumn(col_name.to_string())),
+        }
+    }
+
     // Set the value at the given row-column intersection.
     ///
     /// # Arguments
@@ -294,8 +937,10 @@ impl Document {
     where
         T: Sized + Display,
     {
-        if let Some(row) = self.rows.get_mut(row) {
-            row.replace(column, value);
+        if row < self.rows.len() {
+            self.unindex_row(row);
+            self.rows[row].replace(column, value);
+            self.index_row(row);
         }
     }
 
@@ -308,7 +953,7 @@ impl Document {
     /// # Errors
     /// If the given column name or row index does not exist.
     /// or if the data cannot properly be parsed into the type T.
-    pub fn set_value<T: std::str::FromStr>(&mut self, row: usize, col_name: &str, value: T)
+    pub fn set_value<T>(&mut self, row: usize, col_name: &str, value: T)
     where
         T: Sized + Display,
     {
@@ -317,6 +962,152 @@ impl Document {
         }
     }
 
+    /// Rewrite every value of a column with the given function.
+    ///
+    /// Rows too short to hold the column are left untouched.
+    ///
+    /// # Arguments
+    /// `col_name` name of the column being rewritten.
+    /// `f` function receiving a value and returning its replacement.
+    ///
+    /// # Errors
+    /// If the given column name does not exist in the document.
+    pub fn map_column<F>(&mut self, col_name: &str, f: F) -> Result<()>
+    where
+        F: Fn(&str) -> String,
+    {
+        let column = *self
+            .header_indexes
+            .get(col_name)
+            .ok_or_else(|| CsvError::InvalidColumn(col_name.to_string()))?;
+        for row in &mut self.rows {
+            row.map_field(column, &f);
+        }
+        self.refresh_row_index();
+        Ok(())
+    }
+
+    /// Replace every value of a column, one value per row in order.
+    ///
+    /// # Arguments
+    /// `col_name` name of the column being replaced.
+    /// `values` new values, as many as there are rows.
+    ///
+    /// # Errors
+    /// [`CsvError::InvalidColumn`] if the given column name does not exist in the document.
+    /// [`CsvError::Generic`] if the number of values differs from the number of rows.
+    pub fn set_column<T>(&mut self, col_name: &str, values: &[T]) -> Result<()>
+    where
+        T: Sized + Display,
+    {
+        let column = *self
+            .header_indexes
+            .get(col_name)
+            .ok_or_else(|| CsvError::InvalidColumn(col_name.to_string()))?;
+        if values.len() != self.rows.len() {
+            return Err(CsvError::Generic(format!(
+                "Expected {} values for column `{col_name}`, got {}.",
+                self.rows.len(),
+                values.len()
+            )));
+        }
+        for (row, value) in self.rows.iter_mut().zip(values) {
+            row.replace(column, value);
+        }
+        self.refresh_row_index();
+        Ok(())
+    }
+
+    /// Swap two columns, moving their fields in the header and every row.
+    ///
+    /// # Arguments
+    /// `a` name of the first column.
+    /// `b` name of the second column.
+    ///
+    /// # Errors
+    /// [`CsvError::InvalidColumn`] if one of the columns does not exist.
+    pub fn swap_columns(&mut self, a: &str, b: &str) -> Result<()> {
+        self.check_columns(&[a, b])?;
+        let mut order: Vec<usize> = (0..self.column_count()).collect();
+        order.swap(self.header_indexes[a], self.header_indexes[b]);
+        self.permute_columns(&order);
+        Ok(())
+    }
+
+    /// Reorder every column, moving the fields in the header and every row.
+    ///
+    /// Rows shorter than the header are padded with empty fields,
+    /// fields past the header's width are kept at the end of their row.
+    ///
+    /// # Arguments
+    /// `order` names of every column of the document, in their new order.
+    ///
+    /// # Errors
+    /// [`CsvError::InvalidColumn`] if one of the columns does not exist.
+    /// [`CsvError::Generic`] if the document has no headers, or if `order` does not list
+    /// every column exactly once.
+    pub fn reorder_columns(&mut self, order: &[&str]) -> Result<()> {
+        if self.headers.is_none() {
+            return Err(CsvError::Generic("Document has no headers.".to_string()));
+        }
+        self.check_columns(order)?;
+        let indexes: Vec<usize> = order
+            .iter()
+            .map(|name| self.header_indexes[*name])
+            .collect();
+        let listed: HashSet<&usize> = indexes.iter().collect();
+        if indexes.len() != self.column_count() || listed.len() != indexes.len() {
+            return Err(CsvError::Generic(
+                "Column order must list every column exactly once.".to_string(),
+            ));
+        }
+        self.permute_columns(&indexes);
+        Ok(())
+    }
+
+    /// Moves the fields of the header and every row so field `order[i]` ends up at index `i`.
+    fn permute_columns(&mut self, order: &[usize]) {
+        if let Some(headers) = &self.headers {
+            let headers = permute_row(headers, order);
+            self.header_indexes = Document::with_header_row(Some(headers.clone())).header_indexes;
+            self.headers = Some(headers);
+        }
+        for row in self.rows.iter_mut() {
+            *row = permute_row(row, order);
+        }
+        self.refresh_row_index();
+    }
+
+    /// Replace every empty cell in a column with the closest non-empty value above it.
+    ///
+    /// Empty cells before the first non-empty value are left untouched.
+    ///
+    /// # Arguments
+    /// `col_name` name of the column to fill.
+    ///
+    /// # Errors
+    /// If the given column name does not exist in the document.
+    pub fn fill_down(&mut self, col_name: &str) -> Result<()> {
+        let column = *self
+            .header_indexes
+            .get(col_name)
+            .ok_or_else(|| CsvError::InvalidColumn(col_name.to_string()))?;
+        let mut last_value: Option<String> = None;
+        for row in &mut self.rows {
+            match row.get_range(column) {
+                Some([]) => {
+                    if let Some(value) = &last_value {
+                        row.replace(column, value);
+                    }
+                }
+                Some(value) => last_value = Some(String::from_utf8_lossy(value).to_string()),
+                None => {}
+            }
+        }
+        self.refresh_row_index();
+        Ok(())
+    }
+
     /// Write the contents of this header to the given file.
     ///
     ///  # Arguments path
@@ -341,37 +1132,394 @@ impl Document {
     ///     "jeng@mail.com",
     ///     "Marktown High School"
     /// ]);
-    /// doc.write_to_file("malist.csv")
+    /// let path = std::env::temp_dir().join(format!("malist_{}.csv", std::process::id()));
+    /// doc.write_to_file(&path)
     ///     .expect("Error writing to file");
+    /// # std::fs::remove_file(&path).unwrap();
     /// ``````
     pub fn write_to_file(&self, path: impl AsRef<Path>) -> Result<()> {
-        let mut writer = Writer::from_path(path)?;
+        let file = File::create(path)?;
+        self.write_to(file)
+    }
+
+    /// Write the contents of this document to any [`std::io::Write`] implementation,
+    /// such as stdout, a socket or an in-memory buffer.
+    ///
+    /// # Arguments
+    /// `writer` destination for the header and rows.
+    ///
+    /// # Errors
+    /// If writing fails for IO related reasons.
+    pub fn write_to<W: std::io::Write>(&self, writer: W) -> Result<()> {
+        let mut writer = Writer::from_writer(writer);
         writer.write(&self.get_headers_row())?;
         for row in &self.rows {
             writer.write(row)?;
         }
         Ok(())
     }
+
+    /// Copy a CSV file to another one, keeping only some columns and the rows matching a filter.
+    ///
+    /// Records are streamed one at a time, so the input is never loaded in memory as a whole.
+    ///
+    /// # Arguments
+    /// `input`  path of the CSV file to read, it must have a header.
+    /// `output` path of the CSV file to create.
+    /// `select` names of the columns to keep, in output order.
+    /// `filter` decides whether a row is kept, it sees every column of the input.
+    ///
+    /// # Errors
+    /// If a selected column does not exist in the input.
+    /// If either file cannot be accessed, or writing fails for IO related reasons.
+    pub fn transform_file<F>(input: &Path, output: &Path, select: &[&str], filter: F) -> Result<()>
+    where
+        F: Fn(&DocEntry) -> bool,
+    {
+        let reader = Reader::from_path(input)?;
+        let header_indexes = match reader.headers() {
+            Some(header) => index_headers(&header)?,
+            None => HashMap::new(),
+        };
+        let columns = select
+            .iter()
+            .map(|name| {
+                header_indexes
+                    .get(*name)
+                    .copied()
+                    .ok_or_else(|| CsvError::InvalidColumn(name.to_string()))
+            })
+            .collect::<Result<Vec<usize>>>()?;
+
+        let mut writer = Writer::from_path(output)?;
+        writer.write(&Row::from(select))?;
+        for row in reader.entries() {
+            let entry = DocEntry {
+                row: &row,
+                header_indexes: &header_indexes,
+                missing_as_empty: false,
+            };
+            if !filter(&entry) {
+                continue;
+            }
+            let mut selected = Row::with_capacity(row.inner.len());
+            for column in &columns {
+                selected.add_field_from(&row, *column);
+            }
+            writer.write(&selected)?;
+        }
+        Ok(())
+    }
+
+    /// Write one CSV file per distinct value of the given column, each including the header.
+    ///
+    /// Files are named after the value, replacing any character other than ASCII
+    /// alphanumerics, `-` and `_` with `_`. Values that sanitize to the same name share a file.
+    ///
+    /// # Arguments
+    /// `dir`  directory where the files are created.
+    /// `key_col` name of the column used to partition the rows.
+    ///
+    /// # Returns
+    /// The paths of the created files, in order of first appearance.
+    ///
+    /// # Errors
+    /// If the given column name does not exist in the document
+    /// or if writing to a file fails for IO related reasons.
+    pub fn write_partitioned(&self, dir: &Path, key_col: &str) -> Result<Vec<PathBuf>> {
+        let column = *self
+            .header_indexes
+            .get(key_col)
+            .ok_or_else(|| CsvError::InvalidColumn(key_col.to_string()))?;
+
+        let mut partitions: Vec<(PathBuf, Vec<&Row>)> = Vec::new();
+        let mut partition_indexes: HashMap<PathBuf, usize> = HashMap::new();
+        for row in &self.rows {
+            let value = String::from_utf8_lossy(row.get_range(column).unwrap_or_default());
+            let path = dir.join(format!("{}.csv", sanitize_file_name(&value)));
+            let index = *partition_indexes.entry(path.clone()).or_insert_with(|| {
+                partitions.push((path, Vec::new()));
+                partitions.len() - 1
+            });
+            partitions[index].1.push(row);
+        }
+
+        let mut paths = Vec::with_capacity(partitions.len());
+        for (path, rows) in partitions {
+            let mut writer = Writer::from_path(&path)?;
+            writer.write(&self.get_headers_row())?;
+            for row in rows {
+                writer.write(row)?;
+            }
+            paths.push(path);
+        }
+        Ok(paths)
+    }
+
+    /// Render the document as an HTML `<table>` for quick previews.
+    ///
+    /// Headers go into the `<thead>` and every row into the `<tbody>`.
+    /// Cell contents are HTML escaped.
+    pub fn to_html_table(&self) -> String {
+        let mut html = String::from("<table>\n");
+        if let Some(headers) = &self.headers {
+            html.push_str("<thead>\n<tr>");
+            for field in headers.iter() {
+                html.push_str(&format!("<th>{}</th>", escape_html(field.as_bytes())));
+            }
+            html.push_str("</tr>\n</thead>\n");
+        }
+        html.push_str("<tbody>\n");
+        for row in &self.rows {
+            html.push_str("<tr>");
+            for field in row.iter() {
+                html.push_str(&format!("<td>{}</td>", escape_html(field.as_bytes())));
+            }
+            html.push_str("</tr>\n");
+        }
+        html.push_str("</tbody>\n</table>\n");
+        html
+    }
+
+    /// Print the document to stdout as an aligned, bordered table.
+    ///
+    /// Cells wider than [`DEFAULT_MAX_CELL_WIDTH`] are truncated with an ellipsis.
+    /// See [`Document::format_table`] to configure the width.
+    pub fn print_table(&self) {
+        print!("{}", self.format_table(DEFAULT_MAX_CELL_WIDTH));
+    }
+
+    /// Format the document as an aligned, bordered table.
+    ///
+    /// # Arguments
+    /// `max_width` maximum characters per cell, longer cells are truncated with an ellipsis.
+    ///
+    /// # Example
+    /// ```rust
+    /// use csvlib::Document;
+    /// let mut doc = Document::with_headers(&["Name", "Age"]);
+    /// doc.add_row(csvlib::csv!["Mike", 15]);
+    /// assert_eq!(
+    ///     doc.format_table(10),
+    ///     "+------+-----+\n\
+    ///      | Name | Age |\n\
+    ///      +------+-----+\n\
+    ///      | Mike | 15  |\n\
+    ///      +------+-----+\n"
+    /// );
+    /// ```
+    pub fn format_table(&self, max_width: usize) -> String {
+        let max_width = max_width.max(1);
+        let to_cells = |row: &Row| -> Vec<String> {
+            row.iter()
+                .map(|field| {
+                    let value = String::from_utf8_lossy(field.as_bytes());
+                    if value.chars().count() > max_width {
+                        let mut truncated: String = value.chars().take(max_width - 1).collect();
+                        truncated.push('…');
+                        truncated
+                    } else {
+                        value.to_string()
+                    }
+                })
+                .collect()
+        };
+
+        let header = self.headers.as_ref().map(to_cells);
+        let rows: Vec<Vec<String>> = self.rows.iter().map(to_cells).collect();
+
+        let mut widths: Vec<usize> = Vec::new();
+        for cells in header.iter().chain(rows.iter()) {
+            for (index, cell) in cells.iter().enumerate() {
+                let width = cell.chars().count();
+                match widths.get_mut(index) {
+                    Some(current) => *current = (*current).max(width),
+                    None => widths.push(width),
+                }
+            }
+        }
+
+        let border: String = widths
+            .iter()
+            .map(|width| format!("+{}", "-".repeat(width + 2)))
+            .collect::<String>()
+            + "+\n";
+        let format_line = |cells: &Vec<String>| -> String {
+            let mut line = String::new();
+            for (index, width) in widths.iter().enumerate() {
+                let cell = cells.get(index).map(String::as_str).unwrap_or_default();
+                let padding = width - cell.chars().count();
+                line.push_str(&format!("| {cell}{} ", " ".repeat(padding)));
+            }
+            line + "|\n"
+        };
+
+        let mut table = border.clone();
+        if let Some(header) = &header {
+            table.push_str(&format_line(header));
+            table.push_str(&border);
+        }
+        for cells in &rows {
+            table.push_str(&format_line(cells));
+        }
+        if !rows.is_empty() {
+            table.push_str(&border);
+        }
+        table
+    }
+}
+
+/// Maps every header name to its column index.
+fn index_headers(header: &Row) -> Result<HashMap<String, usize>> {
+    let mut header_indexes = HashMap::new();
+    for (index, value) in header.iter().enumerate() {
+        let header_string_value = value.to_string().map_err(|_| {
+            CsvError::ConversionError(index, std::any::type_name::<String>().to_owned())
+        })?;
+        header_indexes.insert(header_string_value, index);
+    }
+    Ok(header_indexes)
 }
 
-impl TryFrom<Reader<File>> for Document {
+/// Builds a row holding field `order[i]` at index `i`, followed by the fields left out of `order`.
+fn permute_row(row: &Row, order: &[usize]) -> Row {
+    let mut permuted = Row::with_capacity(row.inner.len());
+    permuted.delimiter(row.delim);
+    for index in order {
+        permuted.add_field_from(row, *index);
+    }
+    for index in order.len()..row.count() {
+        permuted.add_field_from(row, index);
+    }
+    permuted
+}
+
+/// Lists the columns of a header index, sorted by their position.
+fn sorted_columns(header_indexes: &HashMap<String, usize>) -> Vec<(&String, usize)> {
+    let mut columns: Vec<(&String, usize)> = header_indexes
+        .iter()
+        .map(|(name, index)| (name, *index))
+        .collect();
+    columns.sort_by_key(|(_, index)| *index);
+    columns
+}
+
+/// Formats an entry as its columns and values in header order, values are decoded lossily.
+fn fmt_entry(
+    f: &mut std::fmt::Formatter<'_>,
+    name: &str,
+    row: &Row,
+    header_indexes: &HashMap<String, usize>,
+) -> std::fmt::Result {
+    let mut entry = f.debug_struct(name);
+    for (column, index) in sorted_columns(header_indexes) {
+        let value = row.get_range(index).unwrap_or_default();
+        entry.field(column, &String::from_utf8_lossy(value));
+    }
+    entry.finish()
+}
+
+/// Hashes the fields of a row, ignoring its delimiter like [`Row::content_eq`].
+fn row_hash(row: &Row) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    row.inner.hash(&mut hasher);
+    row.ranges.hash(&mut hasher);
+    row.nulls.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Lossily converts every field of a row to a string.
+fn row_to_strings(row: &Row) -> Vec<String> {
+    (0..row.count())
+        .filter_map(|index| row.get_range(index))
+        .map(|field| String::from_utf8_lossy(field).to_string())
+        .collect()
+}
+
+/// Reads a cell from a row, treating a field missing from a short row as empty when requested.
+fn get_cell<T: FromStr>(row: &Row, column: usize, missing_as_empty: bool) -> Result<T> {
+    match row.get::<T>(column) {
+        Err(CsvError::NotAField(_)) if missing_as_empty => ""
+            .parse::<T>()
+            .map_err(|_| CsvError::ConversionError(column, std::any::type_name::<T>().to_string())),
+        result => result,
+    }
+}
+
+/// Turn a field value into a safe file name.
+fn sanitize_file_name(value: &str) -> String {
+    let name: String = value
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if name.is_empty() {
+        "_".to_string()
+    } else {
+        name
+    }
+}
+
+/// Escape the characters that have special meaning in HTML.
+fn escape_html(bytes: &[u8]) -> String {
+    let mut escaped = String::with_capacity(bytes.len());
+    for c in String::from_utf8_lossy(bytes).chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+// The row index is a cache, so it is left out of comparisons and debug output
+impl PartialEq for Document {
+    fn eq(&self, other: &Self) -> bool {
+        self.headers == other.headers
+            && self.rows == other.rows
+            && self.header_indexes == other.header_indexes
+            && self.load_errors == other.load_errors
+            && self.missing_as_empty == other.missing_as_empty
+    }
+}
+
+impl std::fmt::Debug for Document {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Document")
+            .field("headers", &self.headers)
+            .field("rows", &self.rows)
+            .field("header_indexes", &self.header_indexes)
+            .field("load_errors", &self.load_errors)
+            .field("missing_as_empty", &self.missing_as_empty)
+            .finish()
+    }
+}
+
+impl<R: std::io::Read> TryFrom<Reader<R>> for Document {
     type Error = CsvError;
-    fn try_from(reader: Reader<File>) -> Result<Self> {
+    fn try_from(reader: Reader<R>) -> Result<Self> {
         let headers = reader.headers();
         let rows = reader.entries().collect();
-        let mut header_indexes = HashMap::new();
-        if let Some(header) = &headers {
-            for (index, value) in header.iter().enumerate() {
-                let header_string_value = value.to_string().map_err(|_| {
-                    CsvError::ConversionError(index, std::any::type_name::<String>().to_owned())
-                })?;
-                header_indexes.insert(header_string_value, index);
-            }
-        }
+        let header_indexes = match &headers {
+            Some(header) => index_headers(header)?,
+            None => HashMap::new(),
+        };
         Ok(Document {
             headers,
             rows,
             header_indexes,
+            load_errors: 0,
+            missing_as_empty: false,
+            row_index: None,
         })
     }
 }
@@ -379,6 +1527,7 @@ impl TryFrom<Reader<File>> for Document {
 pub struct DocEntry<'a> {
     pub(crate) row: &'a Row,
     pub(crate) header_indexes: &'a HashMap<String, usize>,
+    pub(crate) missing_as_empty: bool,
 }
 
 impl<'a> DocEntry<'a> {
@@ -392,16 +1541,46 @@ impl<'a> DocEntry<'a> {
     /// or if the data cannot properly be parsed into the type T.
     pub fn get_value<T: std::str::FromStr>(&self, col_name: &str) -> Result<T> {
         if let Some(col_index) = self.header_indexes.get(col_name) {
-            self.row.get::<T>(*col_index)
+            get_cell(self.row, *col_index, self.missing_as_empty)
         } else {
             Err(CsvError::InvalidColumn(col_name.to_string()))
         }
     }
+
+    /// Get the raw bytes at the current row-column intersection, without any UTF-8 decoding.
+    ///
+    /// # Arguments
+    /// `col_name` name of the column being searched.
+    ///
+    /// # Returns
+    /// The field bytes, or `None` if the column does not exist or the row is too short.
+    pub fn get_bytes(&self, col_name: &str) -> Option<&'a [u8]> {
+        self.row.get_range(*self.header_indexes.get(col_name)?)
+    }
+
+    /// Get every value of the row as strings, in header order.
+    ///
+    /// # Returns
+    /// One value per header, empty for fields missing from the row. Invalid UTF-8 is
+    /// decoded lossily, replacing bad bytes with `U+FFFD`.
+    pub fn values(&self) -> Vec<String> {
+        sorted_columns(self.header_indexes)
+            .into_iter()
+            .map(|(name, _)| self.get_value(name).unwrap_or_default())
+            .collect()
+    }
+}
+
+impl std::fmt::Debug for DocEntry<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fmt_entry(f, "DocEntry", self.row, self.header_indexes)
+    }
 }
 
 pub struct DocIter<'a> {
     iter: Iter<'a, Row>,
     pub(crate) header_indexes: &'a HashMap<String, usize>,
+    pub(crate) missing_as_empty: bool,
 }
 
 impl<'a> Iterator for DocIter<'a> {
@@ -410,8 +1589,9 @@ impl<'a> Iterator for DocIter<'a> {
     fn next(&mut self) -> Option<Self::Item> {
         if let Some(row) = self.iter.next() {
             Some(DocEntry {
-                row: row,
+                row,
                 header_indexes: self.header_indexes,
+                missing_as_empty: self.missing_as_empty,
             })
         } else {
             None
@@ -421,6 +1601,7 @@ impl<'a> Iterator for DocIter<'a> {
 pub struct DocEntryMut<'a> {
     pub(crate) row: &'a mut Row,
     pub(crate) header_indexes: &'a HashMap<String, usize>,
+    pub(crate) missing_as_empty: bool,
 }
 
 impl<'a> DocEntryMut<'a> {
@@ -434,7 +1615,7 @@ impl<'a> DocEntryMut<'a> {
     /// or if the data cannot properly be parsed into the type T.
     pub fn get_value<T: std::str::FromStr>(&self, col_name: &str) -> Result<T> {
         if let Some(col_index) = self.header_indexes.get(col_name) {
-            self.row.get::<T>(*col_index)
+            get_cell(self.row, *col_index, self.missing_as_empty)
         } else {
             Err(CsvError::InvalidColumn(col_name.to_string()))
         }
@@ -458,9 +1639,16 @@ impl<'a> DocEntryMut<'a> {
     }
 }
 
+impl std::fmt::Debug for DocEntryMut<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fmt_entry(f, "DocEntryMut", self.row, self.header_indexes)
+    }
+}
+
 pub struct DocIterMut<'a> {
     iter: IterMut<'a, Row>,
     pub(crate) header_indexes: &'a HashMap<String, usize>,
+    pub(crate) missing_as_empty: bool,
 }
 
 impl<'a> Iterator for DocIterMut<'a> {
@@ -469,8 +1657,9 @@ impl<'a> Iterator for DocIterMut<'a> {
     fn next(&mut self) -> Option<Self::Item> {
         if let Some(row) = self.iter.next() {
             Some(DocEntryMut {
-                row: row,
+                row,
                 header_indexes: self.header_indexes,
+                missing_as_empty: self.missing_as_empty,
             })
         } else {
             None
@@ -107,9 +107,33 @@ impl Field {
     /// # Errors
     /// If the bytes inside of the field cannot be parsed into a valid UTF8 String
     pub fn to_string(&self) -> Result<String> {
+        self.try_to_string()
+    }
+
+    /// Convert the Field into a String without any lossy UTF8 fallback.
+    ///
+    /// Same as [`Field::to_string`], but named so it cannot be confused with
+    /// [`ToString::to_string`]. Use it to detect corrupted data.
+    ///
+    /// # Errors
+    /// [`CsvError::InvalidString`] if the bytes inside of the field are not valid UTF8.
+    pub fn try_to_string(&self) -> Result<String> {
         String::from_utf8(self.inner.clone()).map_err(|_| CsvError::InvalidString)
     }
 
+    /// Borrow the Field as a string slice without copying or lossy UTF8 fallback.
+    ///
+    /// # Errors
+    /// [`CsvError::InvalidString`] if the bytes inside of the field are not valid UTF8.
+    pub fn try_str(&self) -> Result<&str> {
+        std::str::from_utf8(&self.inner).map_err(|_| CsvError::InvalidString)
+    }
+
+    /// Checks whether the Field holds no bytes.
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
     /// Cast field into a given type.
     ///
     /// If the parsing is not possible, a result with an error is returned.
@@ -122,6 +146,117 @@ impl Field {
             .parse::<T>()
             .map_err(|_| CsvError::FieldParseError(type_name::<T>().to_string()))
     }
+
+    /// Cast field into a given type, ignoring surrounding ASCII whitespace.
+    ///
+    /// Useful for human edited files where values like `" 42 "` are common.
+    ///
+    /// # Errors
+    /// If the bytes inside the field cannot be parsed into valid UTF8 strings.
+    /// If the trimmed field cannot be parsed into the type specified for conversion
+    pub fn cast_trimmed<T: FromStr>(&self) -> Result<T> {
+        self.to_string()?
+            .trim_matches(|c: char| c.is_ascii_whitespace())
+            .parse::<T>()
+            .map_err(|_| CsvError::FieldParseError(type_name::<T>().to_string()))
+    }
+
+    /// Encode the field for network framing: a little endian `u32` length followed by the bytes.
+    ///
+    /// # Errors
+    /// If the field is too long for its length to fit in a `u32`, 4 GiB or more.
+    pub fn encode_framed(&self) -> Result<Vec<u8>> {
+        let length = u32::try_from(self.inner.len())
+            .map_err(|_| CsvError::Generic("Field too long to be framed.".to_string()))?;
+        let mut framed = Vec::with_capacity(4 + self.inner.len());
+        framed.extend_from_slice(&length.to_le_bytes());
+        framed.extend_from_slice(&self.inner);
+        Ok(framed)
+    }
+
+    /// Decode a field produced by [`Field::encode_framed`] from the start of `bytes`.
+    ///
+    /// # Returns
+    /// The decoded field and the number of bytes it took, so the next frame starts right after.
+    ///
+    /// # Errors
+    /// If `bytes` is shorter than the length prefix or the length it announces.
+    ///
+    /// # Examples:
+    /// ```
+    /// # use csvlib::Field;
+    /// let framed = Field::from("café").encode_framed().unwrap();
+    /// let (field, used) = Field::decode_framed(&framed).unwrap();
+    /// assert_eq!(field, Field::from("café"));
+    /// assert_eq!(used, framed.len());
+    /// ```
+    pub fn decode_framed(bytes: &[u8]) -> Result<(Field, usize)> {
+        let truncated = || CsvError::Generic("Truncated framed field.".to_string());
+        let prefix: [u8; 4] = bytes
+            .get(..4)
+            .and_then(|prefix| prefix.try_into().ok())
+            .ok_or_else(truncated)?;
+        let end = usize::try_from(u32::from_le_bytes(prefix))
+            .ok()
+            .and_then(|length| length.checked_add(4))
+            .ok_or_else(truncated)?;
+        let inner = bytes.get(4..end).ok_or_else(truncated)?;
+        Ok((Field::new(inner), end))
+    }
+
+    /// Escape the field as it would appear inside a CSV line using the given delimiter.
+    ///
+    /// The field is wrapped in quotes when it contains the delimiter or a quote, and inner
+    /// quotes are doubled, exactly like [`Writer`] does.
+    ///
+    /// # Arguments
+    /// `delim` delimiter of the line the fragment is meant for.
+    ///
+    /// # Examples:
+    /// ```
+    /// # use csvlib::Field;
+    /// assert_eq!(Field::from("a,b").to_csv_fragment(','), "\"a,b\"");
+    /// ```
+    pub fn to_csv_fragment(&self, delim: char) -> String {
+        let mut fragment = Vec::with_capacity(self.inner.len() + 2);
+        let mut buffer = [0; 4];
+        let delim = delim.encode_utf8(&mut buffer).as_bytes();
+        // Writing into a Vec cannot fail
+        let _ = write_escaped_field(&mut fragment, &self.inner, delim, QUOTE, false);
+        String::from_utf8_lossy(&fragment).to_string()
+    }
+
+    /// Cast field into a boolean using custom truthy and falsy tokens.
+    ///
+    /// Matching is case-insensitive, so `&["y", "yes", "1"]` accepts `Y` and `YES` too.
+    ///
+    /// # Arguments
+    /// `truthy` tokens that represent `true`.
+    /// `falsy` tokens that represent `false`.
+    ///
+    /// # Errors
+    /// If the bytes inside the field cannot be parsed into valid UTF8 strings.
+    /// If the field matches none of the given tokens.
+    ///
+    /// # Examples:
+    /// ```
+    /// # use csvlib::Field;
+    /// let field = Field::from("Yes");
+    /// assert!(field.cast_bool(&["y", "yes"], &["n", "no"]).unwrap());
+    /// ```
+    pub fn cast_bool(&self, truthy: &[&str], falsy: &[&str]) -> Result<bool> {
+        let value = self.to_string()?;
+        if truthy
+            .iter()
+            .any(|token| token.eq_ignore_ascii_case(&value))
+        {
+            Ok(true)
+        } else if falsy.iter().any(|token| token.eq_ignore_ascii_case(&value)) {
+            Ok(false)
+        } else {
+            Err(CsvError::FieldParseError(type_name::<bool>().to_string()))
+        }
+    }
 }
 
 impl FromStr for Field {
@@ -137,12 +272,64 @@ impl From<&str> for Field {
     }
 }
 
+impl PartialEq<i64> for Field {
+    /// Parses the field and compares it numerically, a field that is not an integer never matches.
+    fn eq(&self, other: &i64) -> bool {
+        self.cast::<i64>().is_ok_and(|value| value == *other)
+    }
+}
+
+impl PartialEq<f64> for Field {
+    /// Parses the field and compares it numerically, a field that is not a number never matches.
+    fn eq(&self, other: &f64) -> bool {
+        self.cast::<f64>().is_ok_and(|value| value == *other)
+    }
+}
+
 impl std::fmt::Display for Field {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.to_string().map_err(|_| std::fmt::Error)?)
     }
 }
 
+/// Writes a field escaped for CSV, wrapping it in quotes when it contains the delimiter, a quote or a line break.
+///
+/// Quotes inside the field are doubled. `force_quotes` wraps the field even if it needs no escaping.
+fn write_escaped_field(
+    out: &mut impl io::Write,
+    field: &[u8],
+    delimiter: &[u8],
+    quote: u8,
+    force_quotes: bool,
+) -> io::Result<()> {
+    if field.contains(&quote) {
+        // When we have quotes, we escape each quote and put quotes around the field itself
+        out.write_all(&[quote])?;
+        for byte in field {
+            if byte == &quote {
+                // escape the quote!
+                out.write_all(&[*byte, quote])?;
+            } else {
+                out.write_all(&[*byte])?;
+            }
+        }
+        out.write_all(&[quote])
+    } else if force_quotes
+        || field.contains(&CR)
+        || field.contains(&LF)
+        || field
+            .windows(delimiter.len())
+            .any(|window| window == delimiter)
+    {
+        // If the delimiter or a line break is part of the field then let's escape the field
+        out.write_all(&[quote])?;
+        out.write_all(field)?;
+        out.write_all(&[quote])
+    } else {
+        out.write_all(field)
+    }
+}
+
 /// A CSV row which may contain several CSV Fields
 ///
 /// See [`Field`]
@@ -151,6 +338,8 @@ pub struct Row {
     inner: Vec<u8>,
     ranges: Vec<(usize, usize)>,
     delim: char,
+    /// Sorted indexes of the fields holding no value, see [`Row::add_null`].
+    nulls: Vec<usize>,
 }
 
 impl Default for Row {
@@ -159,6 +348,7 @@ impl Default for Row {
             inner: Vec::new(),
             ranges: Vec::new(),
             delim: DEFAULT_DELIM,
+            nulls: Vec::new(),
         }
     }
 }
@@ -179,6 +369,7 @@ impl Row {
             inner: Vec::with_capacity(size),
             ranges: Vec::new(),
             delim: DEFAULT_DELIM,
+            nulls: Vec::new(),
         }
     }
 
@@ -189,6 +380,23 @@ impl Row {
         self.delim = delim;
     }
 
+    /// Get the row's delimiter
+    pub fn delim(&self) -> char {
+        self.delim
+    }
+
+    /// Get the `(start, end)` byte range of every field within the row's bytes.
+    ///
+    /// Together with [`Row::as_bytes`] this allows reading fields without allocating.
+    pub fn ranges(&self) -> &[(usize, usize)] {
+        &self.ranges
+    }
+
+    /// Get the bytes of every field of the row, one after the other, see [`Row::ranges`].
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.inner
+    }
+
     /// Returns an iterator over the inner fields
     ///
     ///  # Examples
@@ -209,7 +417,7 @@ impl Row {
     /// }
     /// ```
     ///
-    pub fn iter(&self) -> FieldsIter {
+    pub fn iter(&self) -> FieldsIter<'_> {
         FieldsIter {
             row: self,
             index: 0,
@@ -227,6 +435,36 @@ impl Row {
         self.inner.extend_from_slice(field)
     }
 
+    /// Adds a null field, holding no value, to the row.
+    ///
+    /// Null fields read as empty but rows compare them as distinct from empty fields.
+    /// [`Row::get_opt`] gives `None` for them and a [`Writer`] writes them as its first
+    /// null value, unquoted. Readers configured through
+    /// [`reader::ReaderBuilder::with_null_values`] produce them for unquoted null values.
+    pub fn add_null(&mut self) {
+        self.nulls.push(self.ranges.len());
+        self.add_bytes(&[]);
+    }
+
+    /// Adds a copy of another row's field, keeping it null if it was, see [`Row::add_null`].
+    ///
+    /// A field missing from `source` is added as empty.
+    pub(crate) fn add_field_from(&mut self, source: &Row, index: usize) {
+        if source.is_null(index) {
+            self.add_null();
+        } else {
+            self.add_bytes(source.get_range(index).unwrap_or_default());
+        }
+    }
+
+    /// Checks whether the field at the given index is null, see [`Row::add_null`].
+    ///
+    /// # Arguments
+    /// `index` the index of the Field inside the row
+    pub fn is_null(&self, index: usize) -> bool {
+        self.nulls.binary_search(&index).is_ok()
+    }
+
     /// Adds a [`Field`] to the  to the row.
     /// Type conversion is done behind the scines to turn the field into a string.
     /// Thus the field is required to impl the [`Display``] trait.
@@ -241,6 +479,18 @@ impl Row {
         self.add_bytes(string_value.as_bytes());
     }
 
+    /// Adds a [`Field`] to the row like [`Row::add`], returning the index it landed at.
+    ///
+    /// # Arguments
+    /// `field` value being added to the row.
+    pub fn push<T>(&mut self, field: T) -> usize
+    where
+        T: Sized + Display,
+    {
+        self.add(field);
+        self.ranges.len() - 1
+    }
+
     /// Remove a [`Field`] from the row.
     ///
     /// # Arguments:
@@ -265,6 +515,44 @@ impl Row {
         // Now remove the bytes and the range
         self.inner.drain(start..end);
         self.ranges.remove(index);
+        self.nulls.retain(|null| *null != index);
+        for null in self.nulls.iter_mut().filter(|null| **null > index) {
+            *null -= 1;
+        }
+    }
+
+    /// Insert a [`Field`] at the given position, shifting the following fields to the right.
+    ///
+    /// Inserting at `index == count()` behaves like [`Row::add`].
+    ///
+    /// # Arguments:
+    /// `index` position the new field will take within the row
+    /// `field` value being inserted to the row.
+    pub fn insert<T>(&mut self, index: usize, field: T)
+    where
+        T: Sized + Display,
+    {
+        if index > self.ranges.len() {
+            return;
+        }
+        let bytes = format!("{field}").into_bytes();
+        let start = match self.ranges.get(index) {
+            Some((start, _)) => *start,
+            None => self.inner.len(),
+        };
+        let length = bytes.len();
+
+        // Move the following ranges forward by the same amount of bytes being inserted
+        for range in self.ranges.iter_mut().skip(index) {
+            range.0 += length;
+            range.1 += length;
+        }
+
+        self.inner.splice(start..start, bytes);
+        self.ranges.insert(index, (start, start + length));
+        for null in self.nulls.iter_mut().filter(|null| **null >= index) {
+            *null += 1;
+        }
     }
 
     pub fn replace<T>(&mut self, index: usize, new_field: T)
@@ -283,9 +571,74 @@ impl Row {
             }
             row.add_bytes(field.as_bytes());
         }
+        row.nulls = self
+            .nulls
+            .iter()
+            .copied()
+            .filter(|null| *null != index)
+            .collect();
         std::mem::swap(self, &mut row)
     }
 
+    /// Rewrite every field of the row with the given function.
+    ///
+    /// The row is rebuilt once after all fields are mapped, and none of them is null anymore.
+    /// Invalid UTF8 is lossily converted before being handed to `f`.
+    ///
+    /// # Arguments
+    /// `f` function receiving each field and returning its new value.
+    ///
+    /// # Examples:
+    /// ```
+    /// let mut row = csvlib::csv![" a ", "b "];
+    /// row.map_fields(|field| field.trim().to_string());
+    /// assert_eq!(row, csvlib::csv!["a", "b"]);
+    /// ```
+    pub fn map_fields<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&str) -> String,
+    {
+        let mut inner = Vec::with_capacity(self.inner.len());
+        let mut ranges = Vec::with_capacity(self.ranges.len());
+        for (start, end) in &self.ranges {
+            let value = f(&String::from_utf8_lossy(&self.inner[*start..*end]));
+            let start = inner.len();
+            inner.extend_from_slice(value.as_bytes());
+            ranges.push((start, inner.len()));
+        }
+        self.inner = inner;
+        self.ranges = ranges;
+        self.nulls.clear();
+    }
+
+    /// Rewrite a single field of the row with the given function.
+    ///
+    /// Only the bytes of that field are replaced, the following fields are shifted as needed.
+    /// The field is no longer null afterwards. Invalid UTF8 is lossily converted before being handed to `f`.
+    ///
+    /// # Arguments
+    /// `index` the index of the Field inside the row
+    /// `f` function receiving the field and returning its new value.
+    pub fn map_field<F>(&mut self, index: usize, f: F)
+    where
+        F: FnOnce(&str) -> String,
+    {
+        let Some((start, end)) = self.ranges.get(index).copied() else {
+            return;
+        };
+        let value = f(&String::from_utf8_lossy(&self.inner[start..end]));
+        let new_end = start + value.len();
+        self.inner.splice(start..end, value.into_bytes());
+        self.ranges[index].1 = new_end;
+        self.nulls.retain(|null| *null != index);
+
+        // Shift the following ranges by the difference in length
+        for range in self.ranges.iter_mut().skip(index + 1) {
+            range.0 = range.0 + new_end - end;
+            range.1 = range.1 + new_end - end;
+        }
+    }
+
     /// Attempts to retrieve and cast a field to a given type.
     ///
     /// # Arguments
@@ -312,6 +665,125 @@ impl Row {
         }
     }
 
+    /// Attempts to retrieve and cast a field like [`Row::get`], keeping the reason a conversion failed.
+    ///
+    /// Useful to tell text that is not a number apart from a number out of range.
+    ///
+    /// # Arguments
+    /// `index` the index of the Field inside the row
+    ///
+    /// # Errors
+    /// [`CsvError::NotAField`] if the index is out of bounds.
+    /// [`CsvError::DetailedConversionError`] holding the parse error message if the field
+    /// cannot be parsed into the type T.
+    ///
+    /// # Examples:
+    /// ```
+    /// # use csvlib::CsvError;
+    /// let row = csvlib::csv!["abc", 300];
+    /// let Err(CsvError::DetailedConversionError(_, _, cause)) = row.get_checked::<u8>(1) else {
+    ///     panic!("300 does not fit in a u8");
+    /// };
+    /// assert_eq!(cause, "number too large to fit in target type");
+    /// ```
+    pub fn get_checked<T>(&self, index: usize) -> Result<T>
+    where
+        T: std::str::FromStr,
+        T::Err: Display,
+    {
+        let field = self.get_range(index).ok_or(CsvError::NotAField(index))?;
+        String::from_utf8_lossy(field).parse::<T>().map_err(|e| {
+            CsvError::DetailedConversionError(index, type_name::<T>().to_string(), e.to_string())
+        })
+    }
+
+    /// Attempts to retrieve and cast a field, telling a missing field apart from a bad one.
+    ///
+    /// # Arguments
+    /// `index` the index of the Field inside the row
+    ///
+    /// # Returns
+    /// `Ok(None)` if the index is out of bounds, otherwise the casted field.
+    ///
+    /// # Errors
+    /// [`CsvError::ConversionError`] if the field cannot be parsed into the type T.
+    pub fn try_get<T: std::str::FromStr>(&self, index: usize) -> Result<Option<T>> {
+        match self.get::<T>(index) {
+            Ok(value) => Ok(Some(value)),
+            Err(CsvError::NotAField(_)) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Attempts to retrieve and cast a field of a nullable column, where empty means no value.
+    ///
+    /// # Arguments
+    /// `index` the index of the Field inside the row
+    ///
+    /// # Returns
+    /// `Ok(None)` if the field is empty or null, see [`Row::add_null`], otherwise the casted field.
+    ///
+    /// # Errors
+    /// [`CsvError::NotAField`] if the index is out of bounds.
+    /// [`CsvError::ConversionError`] if a non-empty field cannot be parsed into the type T.
+    pub fn get_opt<T: std::str::FromStr>(&self, index: usize) -> Result<Option<T>> {
+        match self.get_range(index) {
+            Some([]) => Ok(None),
+            Some(_) => self.get::<T>(index).map(Some),
+            None => Err(CsvError::NotAField(index)),
+        }
+    }
+
+    /// Retrieves a field as a float, falling back to a default for missing values.
+    ///
+    /// # Arguments
+    /// `index` the index of the Field inside the row
+    /// `default` value returned for missing values.
+    /// `sentinels` values standing for a missing number, such as `N/A` or `-`.
+    ///
+    /// # Returns
+    /// The parsed field, or `default` if the field is empty, matches a sentinel,
+    /// cannot be parsed or the index is out of bounds.
+    pub fn get_float_or(&self, index: usize, default: f64, sentinels: &[&str]) -> f64 {
+        match self.get_range(index) {
+            Some([]) | None => default,
+            Some(field) if sentinels.iter().any(|s| s.as_bytes() == field) => default,
+            Some(_) => self.get(index).unwrap_or(default),
+        }
+    }
+
+    /// Compares the fields of two rows, ignoring their delimiters.
+    ///
+    /// A null field never equals an empty one, see [`Row::add_null`]. The derived `==`
+    /// also compares delimiters, so rows holding the same fields but meant for
+    /// different delimiters are not equal.
+    pub fn content_eq(&self, other: &Row) -> bool {
+        self.inner == other.inner && self.ranges == other.ranges && self.nulls == other.nulls
+    }
+
+    /// Borrow the field at the given index as a string slice, without lossy UTF8 fallback.
+    ///
+    /// # Arguments
+    /// `index` index of the field to retrieve.
+    ///
+    /// # Errors
+    /// [`CsvError::NotAField`] if the index is out of bounds.
+    /// [`CsvError::InvalidString`] if the field is not valid UTF8.
+    pub fn get_str(&self, index: usize) -> Result<&str> {
+        let bytes = self.get_range(index).ok_or(CsvError::NotAField(index))?;
+        std::str::from_utf8(bytes).map_err(|_| CsvError::InvalidString)
+    }
+
+    /// Borrow the field at the given index as a string slice, without allocating.
+    ///
+    /// Non-panicking counterpart of indexing the row.
+    ///
+    /// # Returns
+    /// `None` if the index is out of bounds or the field is not valid UTF8.
+    pub fn field(&self, index: usize) -> Option<&str> {
+        self.get_str(index).ok()
+    }
+
     pub fn get_range(&self, index: usize) -> Option<&[u8]> {
         match self.ranges.get(index) {
             Some((start, end)) => Some(&self.inner[*start..*end]),
@@ -319,10 +791,48 @@ impl Row {
         }
     }
 
+    /// Creates a new row holding only the fields within the given index range.
+    ///
+    /// Out of bounds ranges are clamped to the fields available instead of panicking.
+    ///
+    /// # Arguments
+    /// `range` indexes of the fields to copy into the new row.
+    ///
+    /// # Examples:
+    /// ```
+    /// let row = csvlib::csv!["a", "b", "c", "d"];
+    /// assert_eq!(row.slice(1..3), csvlib::csv!["b", "c"]);
+    /// assert_eq!(row.slice(3..10), csvlib::csv!["d"]);
+    /// ```
+    pub fn slice(&self, range: std::ops::Range<usize>) -> Row {
+        let end = range.end.min(self.ranges.len());
+        let start = range.start.min(end);
+        let mut row = Row::new();
+        row.delim = self.delim;
+        for (index, (field_start, field_end)) in self.ranges[start..end].iter().enumerate() {
+            if self.is_null(start + index) {
+                row.add_null();
+            } else {
+                row.add_bytes(&self.inner[*field_start..*field_end]);
+            }
+        }
+        row
+    }
+
     /// Retrieves the number of [`Field`]s in the row
     pub fn count(&self) -> usize {
         self.ranges.len()
     }
+
+    /// Checks whether the row holds no [`Field`]s.
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// Checks whether every [`Field`] of the row is empty, which includes a row without fields.
+    pub fn is_blank(&self) -> bool {
+        self.ranges.iter().all(|(start, end)| start == end)
+    }
 }
 impl From<&[&str]> for Row {
     fn from(fields: &[&str]) -> Self {
@@ -342,6 +852,20 @@ impl From<&[&String]> for Row {
         row
     }
 }
+impl From<Vec<String>> for Row {
+    fn from(fields: Vec<String>) -> Self {
+        fields.into_iter().collect()
+    }
+}
+impl FromIterator<String> for Row {
+    fn from_iter<I: IntoIterator<Item = String>>(fields: I) -> Self {
+        let mut row = Row::new();
+        for field in fields {
+            row.add_bytes(field.as_bytes());
+        }
+        row
+    }
+}
 
 impl Index<usize> for Row {
     type Output = [u8];
@@ -375,6 +899,106 @@ impl std::fmt::Display for Row {
     }
 }
 
+/// Builds a [`Row`] by column name against a header, so fields always land in header order.
+///
+/// # Example
+/// ```
+/// # use csvlib::{csv, RowBuilder};
+/// let header = csv!["Name", "Age", "City"];
+/// let row = RowBuilder::new(&header)
+///     .with_required(&["Name"])
+///     .set("City", "Paris")
+///     .set("Name", "Ann")
+///     .build()
+///     .unwrap();
+/// assert_eq!(row, csv!["Ann", "", "Paris"]);
+/// ```
+#[derive(Debug)]
+pub struct RowBuilder {
+    names: Vec<String>,
+    fields: Vec<Option<String>>,
+    required: Vec<String>,
+    delim: char,
+    error: Option<CsvError>,
+}
+
+impl RowBuilder {
+    /// Create a builder for rows matching the given header, header names are decoded lossily.
+    ///
+    /// # Arguments
+    /// `header` header row giving the name and position of every column.
+    pub fn new(header: &Row) -> Self {
+        let names: Vec<String> = header
+            .iter()
+            .map(|name| String::from_utf8_lossy(name.as_bytes()).to_string())
+            .collect();
+        Self {
+            fields: vec![None; names.len()],
+            names,
+            required: Vec::new(),
+            delim: header.delim,
+            error: None,
+        }
+    }
+
+    /// Set the columns that must be given a value before the row can be built.
+    ///
+    /// # Arguments
+    /// `columns` names of the required columns.
+    pub fn with_required(mut self, columns: &[&str]) -> Self {
+        self.required = columns.iter().map(|name| name.to_string()).collect();
+        self
+    }
+
+    /// Set the value of a column, replacing any previous value.
+    ///
+    /// An unknown column name makes [`RowBuilder::build`] fail.
+    ///
+    /// # Arguments
+    /// `col_name` name of the column being set.
+    /// `value` value of the field.
+    pub fn set<T: Display>(mut self, col_name: &str, value: T) -> Self {
+        match self.names.iter().position(|name| name == col_name) {
+            Some(index) => self.fields[index] = Some(value.to_string()),
+            None => {
+                self.error
+                    .get_or_insert_with(|| CsvError::InvalidColumn(col_name.to_string()));
+            }
+        }
+        self
+    }
+
+    /// Builds the row, leaving the columns that were not set empty.
+    ///
+    /// # Errors
+    /// [`CsvError::InvalidColumn`] if a value was set on a column missing from the header.
+    /// [`CsvError::Generic`] if a required column was not set.
+    pub fn build(self) -> Result<Row> {
+        if let Some(e) = self.error {
+            return Err(e);
+        }
+        for required in &self.required {
+            let index = self
+                .names
+                .iter()
+                .position(|name| name == required)
+                .ok_or_else(|| CsvError::InvalidColumn(required.clone()))?;
+            if self.fields[index].is_none() {
+                return Err(CsvError::Generic(format!(
+                    "Required column {required} was not set."
+                )));
+            }
+        }
+        let mut row: Row = self
+            .fields
+            .into_iter()
+            .map(Option::unwrap_or_default)
+            .collect();
+        row.delimiter(self.delim);
+        Ok(row)
+    }
+}
+
 pub struct FieldsIter<'a> {
     row: &'a Row,
     index: usize,
@@ -385,12 +1009,13 @@ impl Iterator for FieldsIter<'_> {
 
     fn next(&mut self) -> Option<Self::Item> {
         self.index += 1;
-        self.row.get(self.index - 1).ok()
+        // Build the field from the raw bytes, going through `get` would decode them lossily
+        self.row.get_range(self.index - 1).map(Field::new)
     }
 }
 
 /// Create a CSV [`row`] from a several CSV [`Field`]s.
-/// Defaults to separator comma (',').
+/// Defaults to separator comma (','), a `delim = ...;` prefix sets another one.
 ///
 /// # Examples:
 /// ```
@@ -398,10 +1023,18 @@ impl Iterator for FieldsIter<'_> {
 ///
 /// let header = csvlib::csv!["Header 1", "Header 2", "Header 3"];
 /// let entry1 = csvlib::csv!["This is text", 1.2, 5];
+/// let entry2 = csvlib::csv![delim = ';'; "This is text", 1.2, 5];
 ///
 /// ```
 #[macro_export]
 macro_rules! csv {
+    (delim = $delim:expr; $($e:expr),*) => {
+        {
+            let mut row = $crate::csv![$($e),*];
+            row.delimiter($delim);
+            row
+        }
+    };
     ($($e:expr),*) => {
         {
             let mut row = $crate::Row::new();
@@ -414,15 +1047,22 @@ macro_rules! csv {
 #[derive(Debug, PartialEq)]
 pub enum CsvError {
     RecordError,
-    ReadError,
+    ReadError(Option<IoSource>),
     ConversionError(usize, String),
+    ColumnConversionError(String, usize, String),
+    DetailedConversionError(usize, String, String),
     InvalidString,
     FieldParseError(String),
     NotAField(usize),
-    FileError,
+    FileError(Option<IoSource>),
     InvalidColumn(String),
     InvalidRow(usize),
     InvalidColumnIndex(usize),
+    AmbiguousColumn(String),
+    MalformedRecord(usize, Box<CsvError>),
+    UnterminatedQuote(String),
+    RecordTooLarge(usize, usize),
+    TooManyQuotes(usize, usize),
     Generic(String),
 }
 
@@ -430,33 +1070,72 @@ impl Display for CsvError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             CsvError::RecordError => write!(f, "Error reading CSV row"),
-            CsvError::ReadError => write!(f, "Error reading from source."),
+            CsvError::ReadError(_) => write!(f, "Error reading from source."),
             CsvError::ConversionError(index, type_name) => {
                 write!(f, "Error converting field `{index}` to type `{type_name}`")
             }
+            CsvError::ColumnConversionError(column, row, type_name) => {
+                write!(
+                    f,
+                    "Error converting column `{column}` at row `{row}` to type `{type_name}`"
+                )
+            }
+            CsvError::DetailedConversionError(index, type_name, cause) => {
+                write!(
+                    f,
+                    "Error converting field `{index}` to type `{type_name}`: {cause}"
+                )
+            }
             CsvError::InvalidString => write!(f, "Cannot convert field to a valid string."),
             CsvError::NotAField(index) => write!(f, "Not field at given index `{index}`."),
             CsvError::FieldParseError(type_name) => {
                 write!(f, "Error parsing field to `{type_name}`.")
             }
-            CsvError::FileError => write!(f, "Error accessing file."),
+            CsvError::FileError(_) => write!(f, "Error accessing file."),
             CsvError::InvalidColumn(column) => {
                 write!(f, "Invalid Column: `{column}`. Not found in document.")
             }
             CsvError::InvalidColumnIndex(column) => {
                 write!(f, "Invalid Column: `{column}`. Not found in document.")
             }
+            CsvError::AmbiguousColumn(column) => {
+                write!(
+                    f,
+                    "Ambiguous Column: `{column}`. Matches several columns in document."
+                )
+            }
             CsvError::InvalidRow(row) => {
                 write!(f, "Invalid Row: `{row}`. Not found in document.")
             }
+            CsvError::MalformedRecord(line, error) => {
+                write!(f, "Malformed record at line `{line}`: {error}")
+            }
+            CsvError::UnterminatedQuote(partial) => {
+                write!(
+                    f,
+                    "Quoted field never closed, input ended after `{partial}`"
+                )
+            }
+            CsvError::RecordTooLarge(line, limit) => {
+                write!(
+                    f,
+                    "Record starting at line `{line}` exceeds the size limit of `{limit}` bytes"
+                )
+            }
+            CsvError::TooManyQuotes(line, limit) => {
+                write!(
+                    f,
+                    "Record starting at line `{line}` holds more than `{limit}` consecutive quotes"
+                )
+            }
             CsvError::Generic(msg) => write!(f, "{msg}"),
         }
     }
 }
 
 impl From<io::Error> for CsvError {
-    fn from(_: io::Error) -> Self {
-        CsvError::FileError
+    fn from(e: io::Error) -> Self {
+        CsvError::FileError(Some(IoSource(e)))
     }
 }
 
@@ -466,4 +1145,26 @@ impl From<CsvError> for String {
     }
 }
 
-impl Error for CsvError {}
+impl Error for CsvError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            CsvError::ReadError(Some(source)) | CsvError::FileError(Some(source)) => {
+                Some(&source.0)
+            }
+            CsvError::MalformedRecord(_, error) => Some(error.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+/// The underlying I/O error behind a [`CsvError`], exposed through [`Error::source`].
+///
+/// Sources compare equal when their [`io::ErrorKind`] matches.
+#[derive(Debug)]
+pub struct IoSource(pub io::Error);
+
+impl PartialEq for IoSource {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.kind() == other.0.kind()
+    }
+}
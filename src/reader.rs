@@ -17,7 +17,12 @@
 //! }
 //! ```
 
-use std::{io::BufReader, path::Path};
+use std::{
+    collections::HashMap,
+    io::{BufReader, Seek},
+    path::Path,
+    rc::Rc,
+};
 
 use crate::*;
 
@@ -29,12 +34,314 @@ pub struct Reader<R> {
     header: Option<Row>,
     has_header: bool,
     delimiter: Option<char>,
+    options: ParseOptions,
+    progress: Progress,
+    data_start: Progress,
+    field_buffer: Vec<u8>,
+    line_buffer: Vec<u8>,
+    record: Row,
+}
+
+/// How far into the underlying stream the reader has consumed.
+#[derive(Debug, Default, Clone, Copy)]
+struct Progress {
+    /// Bytes consumed from the stream.
+    bytes: u64,
+    /// Physical lines consumed from the stream.
+    lines: usize,
+}
+
+/// Options controlling how [`read_fields`] assembles each record.
+#[derive(Debug, Default)]
+struct ParseOptions {
+    /// Column indexes to keep in every row, all columns are kept when not set.
+    projection: Option<Vec<usize>>,
+    /// Drop the first column of every row, e.g. an unnamed index column.
+    skip_first_column: bool,
+    /// Delimiter made of several characters, replaces the single character delimiter when set.
+    str_delimiter: Option<Vec<u8>>,
+    /// Quote character used to escape fields, `"` when not set.
+    quote: Option<char>,
+    /// Skip records without any field or with a single empty field, such as blank lines.
+    skip_empty: bool,
+    /// Fail on a quoted field left open at the end of the input instead of keeping it as is.
+    strict_quotes: bool,
+    /// Longest run of consecutive quote characters accepted, unlimited when not set.
+    max_consecutive_quotes: Option<usize>,
+    /// Largest record accepted, in bytes of parsed fields, unlimited when not set.
+    max_record_size: Option<usize>,
+    /// UTF-8 encoded character ending each record, replaces line breaks when set.
+    record_separator: Option<Vec<u8>>,
+    /// Values read as null fields when they appear unquoted.
+    null_values: Vec<Vec<u8>>,
+    /// Encoding of the incoming bytes, fields are always stored as UTF-8.
+    #[cfg(feature = "encoding")]
+    encoding: Encoding,
+}
+
+/// Text encoding of the data given to a [`Reader`], see [`ReaderBuilder::with_encoding`].
+#[cfg(feature = "encoding")]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Encoding {
+    /// UTF-8 data, fields are kept as they are.
+    #[default]
+    Utf8,
+    /// ISO-8859-1 data, every byte is the code point of the same value.
+    Latin1,
 }
 
 impl<R: io::Read> Reader<R> {
     pub fn entries(self) -> Entries<R> {
         Entries::new(self)
     }
+
+    /// Iterates over the records along with the byte offset where each record starts.
+    ///
+    /// Offsets are relative to the stream position the reader was created at, so
+    /// they can later be used to `seek` straight to a record.
+    pub fn entries_with_position(self) -> impl Iterator<Item = (u64, Row)> {
+        let mut entries = self.entries();
+        std::iter::from_fn(move || {
+            let position = entries.owner.progress.bytes;
+            entries.next().map(|row| (position, row))
+        })
+    }
+
+    /// Iterates over the records in documents of `size` rows each, the last one may hold fewer.
+    ///
+    /// Every document carries the reader's header, so huge inputs can be processed a page
+    /// at a time without loading them whole.
+    ///
+    /// # Arguments
+    /// `size` number of rows per document, at least one.
+    pub fn chunks(self, size: usize) -> impl Iterator<Item = Document> {
+        let template = Document::with_header_row(self.headers());
+        let mut entries = self.entries();
+        std::iter::from_fn(move || {
+            let mut document = template.clone();
+            for row in entries.by_ref().take(size.max(1)) {
+                document.add_row(row);
+            }
+            (document.row_count() > 0).then_some(document)
+        })
+    }
+
+    /// Iterates over the records wrapped with the reader's header, so fields can be fetched by name.
+    ///
+    /// The header index is built once and shared by every [`HeaderedRow`], names are decoded lossily.
+    /// Without a header, every lookup by name fails.
+    pub fn entries_with_headers(self) -> impl Iterator<Item = HeaderedRow> {
+        let mut header_indexes = HashMap::new();
+        if let Some(header) = &self.header {
+            for (index, value) in header.iter().enumerate() {
+                header_indexes.insert(String::from_utf8_lossy(value.as_bytes()).to_string(), index);
+            }
+        }
+        let header_indexes = Rc::new(header_indexes);
+        self.entries().map(move |row| HeaderedRow {
+            row,
+            header_indexes: Rc::clone(&header_indexes),
+        })
+    }
+
+    /// Iterates over the records decoded into `T` through its `TryFrom<&Row>` implementation.
+    ///
+    /// Records are decoded by position, unlike [`Document`] decoding through `TryFrom<DocEntry>`
+    /// which looks fields up by header name.
+    ///
+    /// # Examples:
+    /// ```
+    /// use csvlib::{CsvError, FromStr, Reader, Row};
+    ///
+    /// struct Item {
+    ///     price: u32,
+    /// }
+    ///
+    /// impl TryFrom<&Row> for Item {
+    ///     type Error = CsvError;
+    ///     fn try_from(row: &Row) -> Result<Self, Self::Error> {
+    ///         Ok(Item { price: row.get(1)? })
+    ///     }
+    /// }
+    ///
+    /// let reader = Reader::from_str("Item,Price\nApple,2\nPear,3").unwrap();
+    /// let total: u32 = reader.entries_from_row::<Item>().map(|item| item.unwrap().price).sum();
+    /// assert_eq!(total, 5);
+    /// ```
+    pub fn entries_from_row<T>(self) -> impl Iterator<Item = Result<T>>
+    where
+        T: for<'a> TryFrom<&'a Row, Error = CsvError>,
+    {
+        self.entries().map(|row| T::try_from(&row))
+    }
+
+    /// Iterates over the records as JSON objects keyed by header name, with string values.
+    ///
+    /// Fields without a matching header, or every field when there is no header,
+    /// are keyed by their column index instead.
+    ///
+    /// # Errors
+    /// Each item fails with [`CsvError::InvalidString`] if a header or field is not valid UTF-8.
+    #[cfg(feature = "json")]
+    pub fn entries_json(self) -> impl Iterator<Item = Result<serde_json::Value>> {
+        let keys = self
+            .header
+            .as_ref()
+            .map(|header| {
+                header
+                    .iter()
+                    .map(|field| field.try_to_string())
+                    .collect::<Result<Vec<String>>>()
+            })
+            .transpose();
+        let mut entries = self.entries();
+        std::iter::from_fn(move || {
+            let row = entries.next()?;
+            let keys = match &keys {
+                Ok(keys) => keys,
+                Err(_) => return Some(Err(CsvError::InvalidString)),
+            };
+            let mut object = serde_json::Map::new();
+            for (index, field) in row.iter().enumerate() {
+                let key = match keys.as_ref().and_then(|keys| keys.get(index)) {
+                    Some(key) => key.clone(),
+                    None => index.to_string(),
+                };
+                let value = match field.try_to_string() {
+                    Ok(value) => value,
+                    Err(e) => return Some(Err(e)),
+                };
+                object.insert(key, serde_json::Value::String(value));
+            }
+            Some(Ok(serde_json::Value::Object(object)))
+        })
+    }
+
+    /// Drives the parser to the end of the input, collecting every problem instead of stopping at the first.
+    ///
+    /// A record is reported when its field count differs from the header's (or the first
+    /// record's when there is no header), when a field is not valid UTF-8, or when the
+    /// parser rejects it. Reading then carries on with the next line, only a failure of
+    /// the underlying stream ends it early.
+    ///
+    /// # Returns
+    /// The well formed rows, and the line number each bad record starts at along with its error.
+    pub fn validate(mut self) -> (Vec<Row>, Vec<(usize, CsvError)>) {
+        let mut expected_count = self.header.as_ref().map(Row::count);
+        let mut rows = Vec::new();
+        let mut errors = Vec::new();
+        loop {
+            let line = self.progress.lines + 1;
+            match self.read_record() {
+                Ok(row) => match check_record(&row, &mut expected_count) {
+                    Ok(()) => rows.push(row),
+                    Err(e) => errors.push((line, e)),
+                },
+                // Nothing left to read
+                Err(CsvError::RecordError) => break,
+                // The stream itself failed, nothing more can be read from it
+                Err(e @ CsvError::ReadError(_)) => {
+                    errors.push((line, e));
+                    break;
+                }
+                Err(e) => errors.push((line, e)),
+            }
+        }
+        (rows, errors)
+    }
+
+    /// Parses the next record and hands it to `f`, reusing the same internal [`Row`] for every record.
+    ///
+    /// Unlike [`Reader::entries`], no new `Row` is allocated per record, which makes this
+    /// suitable for tight decoding loops.
+    ///
+    /// # Returns
+    /// The value produced by `f`, or `None` once there are no records left.
+    ///
+    /// # Errors
+    /// If reading from the underlying stream fails.
+    ///
+    /// # Examples:
+    /// ```
+    /// use csvlib::{FromStr, Reader};
+    ///
+    /// let mut reader = Reader::from_str("Item,Price\nApple,2\nPear,3").unwrap();
+    /// let mut total = 0;
+    /// while let Some(price) = reader.read_decoded_into(|row| row.get::<u32>(1)).unwrap() {
+    ///     total += price.unwrap();
+    /// }
+    /// assert_eq!(total, 5);
+    /// ```
+    pub fn read_decoded_into<T, F>(&mut self, f: F) -> Result<Option<T>>
+    where
+        F: FnOnce(&Row) -> T,
+    {
+        let mut record = std::mem::take(&mut self.record);
+        let result = match self.read_record_into(&mut record) {
+            Ok(()) => Ok(Some(f(&record))),
+            Err(CsvError::RecordError) => Ok(None),
+            Err(e) => Err(e),
+        };
+        self.record = record;
+        result
+    }
+
+    /// Parses the next record from the underlying stream, keeping track of the bytes consumed.
+    fn read_record(&mut self) -> Result<Row> {
+        let mut row = Row::with_capacity(self.line_buffer.capacity());
+        self.read_record_into(&mut row)?;
+        Ok(row)
+    }
+
+    /// Parses the next record into an existing row, replacing its contents.
+    fn read_record_into(&mut self, row: &mut Row) -> Result<()> {
+        let mut buffer = [0; 4];
+        let delimiter = match &self.options.str_delimiter {
+            Some(delim) => delim.as_slice(),
+            None => self
+                .delimiter
+                .unwrap_or(DEFAULT_DELIM)
+                .encode_utf8(&mut buffer)
+                .as_bytes(),
+        };
+        loop {
+            read_fields(
+                &mut self.reader,
+                delimiter,
+                &self.options,
+                row,
+                &mut self.field_buffer,
+                &mut self.line_buffer,
+                &mut self.progress,
+            )?;
+            let empty = row.count() == 0 || (row.count() == 1 && row.get_range(0) == Some(&[]));
+            if !(self.options.skip_empty && empty) {
+                return Ok(());
+            }
+        }
+    }
+}
+
+impl<R> Reader<R>
+where
+    R: io::Read + io::Seek,
+{
+    /// Seeks the underlying stream back to the first record after the header,
+    /// so the records can be iterated again.
+    ///
+    /// Works on streams that were not at their start when the reader was created,
+    /// anything before that position is never read again.
+    ///
+    /// # Errors
+    /// If seeking the underlying stream fails.
+    pub fn rewind(&mut self) -> Result<()> {
+        // Every byte consumed so far is counted, so the reader started this far back
+        let created_at = self.reader.stream_position()? - self.progress.bytes;
+        self.reader
+            .seek(io::SeekFrom::Start(created_at + self.data_start.bytes))?;
+        self.progress = self.data_start;
+        Ok(())
+    }
 }
 
 impl<R> Reader<R>
@@ -50,6 +357,25 @@ where
     pub fn headers(&self) -> Option<Row> {
         self.header.clone()
     }
+
+    /// Borrows the headers for this reader.
+    ///
+    /// # Errors
+    /// [`CsvError::Generic`] if the reader was not configured to read a header.
+    pub fn try_headers(&self) -> Result<&Row> {
+        self.header.as_ref().ok_or_else(|| {
+            CsvError::Generic("No header was requested for this reader.".to_string())
+        })
+    }
+
+    /// Gives back the buffered stream, positioned right after the last record read.
+    ///
+    /// Bytes already buffered but not parsed yet stay in the returned [`BufReader`], so
+    /// reading from it continues exactly where parsing stopped, e.g. to handle a trailing
+    /// section that is not CSV.
+    pub fn into_inner(self) -> BufReader<R> {
+        self.reader
+    }
 }
 
 impl Reader<std::fs::File> {
@@ -63,21 +389,66 @@ impl Reader<std::fs::File> {
     ///
     ///
     pub fn from_path(path: impl AsRef<Path>) -> Result<Self> {
-        let file = std::fs::File::open(path).map_err(|_| CsvError::FileError)?;
-        let mut reader = BufReader::new(file);
-        let header = read_fields(
-            &mut reader,
-            DEFAULT_DELIM,
-            &mut Vec::with_capacity(100),
-            &mut Vec::with_capacity(100),
+        let file = std::fs::File::open(path).map_err(CsvError::from)?;
+        Reader::builder()
+            .with_reader(file)
+            .with_delimiter(DEFAULT_DELIM)
+            .with_header(true)
+            .build()
+    }
+
+    /// Guess the delimiter of a CSV file from its first lines.
+    ///
+    /// Candidates are `,`, `;`, tab and `|`, counted outside of quoted fields.
+    /// The most frequent one appearing the same number of times on every line wins,
+    /// comma is assumed when none of them is found.
+    ///
+    /// # Arguments
+    /// `path` path to the file being inspected.
+    ///
+    /// # Errors
+    /// If the file cannot be accessed or read.
+    pub fn sniff_delimiter(path: impl AsRef<Path>) -> Result<char> {
+        let mut sample = Vec::new();
+        io::Read::read_to_end(
+            &mut io::Read::take(std::fs::File::open(path)?, SNIFF_SAMPLE_SIZE),
+            &mut sample,
         )?;
+        Ok(detect_delimiter(&sample).unwrap_or(DEFAULT_DELIM))
+    }
 
-        Ok(Reader {
-            reader,
-            header: Some(header),
-            has_header: true,
-            delimiter: Some(DEFAULT_DELIM),
-        })
+    /// Count the records of a CSV file without parsing them into rows.
+    ///
+    /// The file is read like [`Reader::from_path`] does: comma delimited with a header,
+    /// which is not counted. Line breaks inside quoted fields do not end a record.
+    ///
+    /// # Arguments
+    /// `path` path to the file being counted.
+    ///
+    /// # Errors
+    /// If the file cannot be accessed or read.
+    pub fn count_records(path: impl AsRef<Path>) -> Result<usize> {
+        let mut reader = BufReader::new(std::fs::File::open(path)?);
+        let records = count_raw_records(&mut reader, DEFAULT_DELIM as u8)?;
+        Ok(records.saturating_sub(1))
+    }
+}
+
+#[cfg(feature = "gzip")]
+impl Reader<flate2::read::GzDecoder<std::fs::File>> {
+    /// Create a reader from a gzip compressed file path.
+    ///
+    /// The file is decompressed on the fly while reading, comma `,` is assumed
+    /// as delimiter and headers to be present.
+    ///
+    /// # Errors
+    /// If the file is not accessible or the header cannot be read.
+    pub fn from_gzip_path(path: impl AsRef<Path>) -> Result<Self> {
+        let file = std::fs::File::open(path).map_err(CsvError::from)?;
+        Reader::builder()
+            .with_reader(flate2::read::GzDecoder::new(file))
+            .with_header(true)
+            .build()
     }
 }
 
@@ -86,29 +457,31 @@ impl FromStr for Reader<std::io::Cursor<String>> {
 
     fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
         let cursor = std::io::Cursor::new(s.to_owned());
-        let mut reader = BufReader::new(cursor);
-        let header = read_fields(
-            &mut reader,
-            DEFAULT_DELIM,
-            &mut Vec::with_capacity(100),
-            &mut Vec::with_capacity(100),
-        )?;
-
-        Ok(Reader {
-            reader,
-            header: Some(header),
-            has_header: true,
-            delimiter: Some(DEFAULT_DELIM),
-        })
+        Reader::builder()
+            .with_reader(cursor)
+            .with_delimiter(DEFAULT_DELIM)
+            .with_header(true)
+            .build()
     }
 }
 
+/// Initial capacity of the parsing buffers, see [`ReaderBuilder::with_buffer_capacity`].
+const DEFAULT_BUFFER_CAPACITY: usize = 100;
+
+/// Function normalizing header names, see [`ReaderBuilder::with_header_map`].
+type HeaderMap = Box<dyn Fn(&str) -> String>;
+
 /// A CSV Reader builder that allows to read CSV data from files and other steams.
 pub struct ReaderBuilder<R> {
     reader: Option<R>,
     header: Option<Row>,
     has_header: bool,
     delimiter: Option<char>,
+    options: ParseOptions,
+    buffer_capacity: usize,
+    auto_delimiter: bool,
+    header_map: Option<HeaderMap>,
+    skip_lines: usize,
 }
 
 impl<R> ReaderBuilder<R> {
@@ -125,6 +498,11 @@ impl<R> Default for ReaderBuilder<R> {
             header: None,
             has_header: false,
             delimiter: None,
+            options: ParseOptions::default(),
+            buffer_capacity: DEFAULT_BUFFER_CAPACITY,
+            auto_delimiter: false,
+            header_map: None,
+            skip_lines: 0,
         }
     }
 }
@@ -140,6 +518,9 @@ where
     /// # Returns
     /// A Result with either a Reader or an Error in case the reader returns errors upon creation.
     ///
+    /// # Errors
+    /// [`CsvError::Generic`] if the quote character given to [`ReaderBuilder::with_quote`] is not ASCII.
+    ///
     /// # Examples:
     /// ```no_run
     /// # use csvlib::Reader;
@@ -148,31 +529,58 @@ where
     ///     .unwrap();
     /// println!("{}", csv_reader.headers().unwrap());
     /// ```
-    pub fn build(mut self) -> Result<Reader<R>> {
+    pub fn build(self) -> Result<Reader<R>> {
+        if let Some(quote) = self.options.quote.filter(|quote| !quote.is_ascii()) {
+            return Err(CsvError::Generic(format!(
+                "Quote character `{quote}` must be ASCII."
+            )));
+        }
         match self.reader {
             Some(reader) => {
-                let mut reader = BufReader::new(reader);
-                let delimiter = match self.delimiter {
-                    Some(delim) => delim,
-                    _ => DEFAULT_DELIM,
-                };
-                if self.has_header {
-                    self.header = Some(read_fields(
-                        &mut reader,
-                        delimiter,
-                        &mut Vec::with_capacity(100),
-                        &mut Vec::with_capacity(100),
-                    )?);
-                }
-
-                Ok(Reader {
-                    reader,
+                let mut reader = Reader {
+                    reader: BufReader::new(reader),
                     header: self.header,
                     has_header: self.has_header,
                     delimiter: self.delimiter,
-                })
+                    options: self.options,
+                    progress: Progress::default(),
+                    data_start: Progress::default(),
+                    field_buffer: Vec::with_capacity(self.buffer_capacity),
+                    line_buffer: Vec::with_capacity(self.buffer_capacity),
+                    record: Row::new(),
+                };
+                for _ in 0..self.skip_lines {
+                    reader.line_buffer.clear();
+                    let n = read_line(&mut reader.reader, &mut reader.line_buffer)
+                        .map_err(|e| CsvError::ReadError(Some(IoSource(e))))?;
+                    if n == 0 {
+                        break;
+                    }
+                    reader.progress.bytes += n as u64;
+                    reader.progress.lines += 1;
+                }
+                reader.data_start = reader.progress;
+                if self.auto_delimiter && reader.delimiter.is_none() {
+                    // Peek at the start of the stream without consuming it
+                    let sample = io::BufRead::fill_buf(&mut reader.reader)
+                        .map_err(|e| CsvError::ReadError(Some(IoSource(e))))?;
+                    reader.delimiter = detect_delimiter(sample);
+                }
+                if reader.has_header {
+                    let header = reader.read_record()?;
+                    reader.header = Some(match &self.header_map {
+                        Some(map) => (0..header.count())
+                            .filter_map(|index| header.get_range(index))
+                            .map(|field| map(&String::from_utf8_lossy(field)))
+                            .collect(),
+                        None => header,
+                    });
+                    reader.data_start = reader.progress;
+                }
+
+                Ok(reader)
             }
-            _ => Err(CsvError::ReadError),
+            _ => Err(CsvError::ReadError(None)),
         }
     }
 
@@ -184,6 +592,69 @@ where
         self
     }
 
+    /// Build Reader with a delimiter made of several characters, such as `::` or `||`.
+    ///
+    /// Takes precedence over [`ReaderBuilder::with_delimiter`]. An empty delimiter is ignored.
+    ///
+    /// # Arguments:
+    /// `delim` string delimiter to be used.
+    pub fn with_str_delimiter(mut self, delim: &str) -> Self {
+        if !delim.is_empty() {
+            self.options.str_delimiter = Some(delim.as_bytes().to_vec());
+        }
+        self
+    }
+
+    /// Sets whether the delimiter is detected from the first lines of the data.
+    ///
+    /// A delimiter given through [`ReaderBuilder::with_delimiter`] takes precedence.
+    /// See [`Reader::sniff_delimiter`] for how the delimiter is picked.
+    ///
+    /// # Arguments:
+    /// `enabled` boolean whether the delimiter is detected.
+    pub fn with_auto_delimiter(mut self, enabled: bool) -> Self {
+        self.auto_delimiter = enabled;
+        self
+    }
+
+    /// Set the values read as null fields, see [`Row::add_null`].
+    ///
+    /// Only unquoted fields are checked, so a quoted `"NA"` written by a [`Writer`] configured
+    /// with the same null values reads back as the text `NA`.
+    ///
+    /// # Arguments:
+    /// `null_values` sentinel values considered null, e.g. `&["NA", "NULL"]`.
+    pub fn with_null_values(mut self, null_values: &[&str]) -> Self {
+        self.options.null_values = null_values
+            .iter()
+            .map(|value| value.as_bytes().to_vec())
+            .collect();
+        self
+    }
+
+    /// Build Reader ending records on a custom character instead of line breaks.
+    ///
+    /// Along with [`ReaderBuilder::with_delimiter`], this reads files using the ASCII record
+    /// (`\u{1e}`) and unit (`\u{1f}`) separators. Line breaks are then regular field content.
+    ///
+    /// # Arguments:
+    /// `separator` character ending each record, matched on its full UTF-8 encoding.
+    pub fn with_record_separator(mut self, separator: char) -> Self {
+        self.options.record_separator = Some(separator.to_string().into_bytes());
+        self
+    }
+
+    /// Skips a number of leading lines, such as a banner, before the header or first record.
+    ///
+    /// Lines are discarded as raw text, quotes inside them are not interpreted.
+    ///
+    /// # Arguments:
+    /// `lines` number of lines discarded from the start of the data.
+    pub fn with_skip_lines(mut self, lines: usize) -> Self {
+        self.skip_lines = lines;
+        self
+    }
+
     /// Sets whether the given reader contains a header line.
     ///
     /// # Arguments:
@@ -193,6 +664,124 @@ where
         self
     }
 
+    /// Only keep the given columns in every row, header included.
+    ///
+    /// The other columns are skipped while parsing. Kept columns retain their original order.
+    ///
+    /// # Arguments:
+    /// `columns` indexes of the columns to keep.
+    pub fn with_projection(mut self, columns: Vec<usize>) -> Self {
+        self.options.projection = Some(columns);
+        self
+    }
+
+    /// Sets whether the first column of every row, header included, should be dropped.
+    ///
+    /// Useful for exports with a leading unnamed index column.
+    ///
+    /// # Arguments:
+    /// `skip` boolean whether the first column is dropped
+    pub fn with_skip_first_column(mut self, skip: bool) -> Self {
+        self.options.skip_first_column = skip;
+        self
+    }
+
+    /// Sets the encoding of the incoming data, fields are transcoded to UTF-8 while parsing.
+    ///
+    /// # Arguments:
+    /// `encoding` encoding the underlying reader's bytes are in.
+    #[cfg(feature = "encoding")]
+    pub fn with_encoding(mut self, encoding: Encoding) -> Self {
+        self.options.encoding = encoding;
+        self
+    }
+
+    /// Build Reader with a custom quote character. If not given, defaults to double quote ('"').
+    ///
+    /// A quote character that is not ASCII makes [`ReaderBuilder::build`] fail.
+    ///
+    /// # Arguments:
+    /// `quote` ASCII character used to quote fields.
+    pub fn with_quote(mut self, quote: char) -> Self {
+        self.options.quote = Some(quote);
+        self
+    }
+
+    /// Sets whether empty records, such as blank lines, are skipped.
+    ///
+    /// A record is empty when it has no fields or a single empty field.
+    /// They are kept by default.
+    ///
+    /// # Arguments:
+    /// `skip` boolean whether empty records are skipped
+    pub fn with_skip_empty(mut self, skip: bool) -> Self {
+        self.options.skip_empty = skip;
+        self
+    }
+
+    /// Sets whether a quoted field still open at the end of the input is an error.
+    ///
+    /// By default the partial field is kept as is, line breaks included. When strict, the
+    /// record fails with [`CsvError::UnterminatedQuote`] holding the partial field instead.
+    ///
+    /// # Arguments:
+    /// `strict` boolean whether unterminated quotes are rejected.
+    pub fn with_strict_quotes(mut self, strict: bool) -> Self {
+        self.options.strict_quotes = strict;
+        self
+    }
+
+    /// Reject records holding a run of consecutive quotes longer than the given limit.
+    ///
+    /// Guards against pathological inputs made of huge runs of quotes. Offending records
+    /// fail with [`CsvError::TooManyQuotes`] holding the line they start at and the limit.
+    ///
+    /// # Arguments:
+    /// `limit` longest run of consecutive quote characters accepted.
+    pub fn with_max_consecutive_quotes(mut self, limit: usize) -> Self {
+        self.options.max_consecutive_quotes = Some(limit);
+        self
+    }
+
+    /// Reject records growing past the given size while they are assembled.
+    ///
+    /// Guards against untrusted inputs, such as a huge unterminated quoted field, exhausting
+    /// memory. The size is checked once per physical line, offending records fail with
+    /// [`CsvError::RecordTooLarge`] holding the line they start at and the limit.
+    ///
+    /// # Arguments:
+    /// `limit` largest record accepted, in bytes of parsed fields.
+    pub fn with_max_record_size(mut self, limit: usize) -> Self {
+        self.options.max_record_size = Some(limit);
+        self
+    }
+
+    /// Sets the initial capacity, in bytes, of the buffers used while parsing.
+    ///
+    /// Defaults to 100. Pre-sizing avoids repeated reallocations on very wide rows.
+    ///
+    /// # Arguments:
+    /// `capacity` initial capacity of the field and line buffers.
+    pub fn with_buffer_capacity(mut self, capacity: usize) -> Self {
+        self.buffer_capacity = capacity;
+        self
+    }
+
+    /// Sets a function normalizing every header name right after the header is parsed.
+    ///
+    /// Useful to lowercase, trim or otherwise canonicalize names before a [`Document`]
+    /// indexes its columns by them.
+    ///
+    /// # Arguments:
+    /// `map` function turning a parsed header name into the name kept.
+    pub fn with_header_map<F>(mut self, map: F) -> Self
+    where
+        F: Fn(&str) -> String + 'static,
+    {
+        self.header_map = Some(Box::new(map));
+        self
+    }
+
     /// Sets the reader interface for this Reader.
     ///
     /// # Arguments:
@@ -224,18 +813,43 @@ where
     R: io::Read,
 {
     owner: Reader<R>,
-
-    line_buffer: Vec<u8>,
-
-    field_buffer: Vec<u8>,
 }
 impl<R: io::Read> Entries<R> {
     fn new(owner: Reader<R>) -> Self {
-        Self {
-            owner,
-            line_buffer: Vec::with_capacity(100),
-            field_buffer: Vec::with_capacity(100),
-        }
+        Self { owner }
+    }
+
+    /// Keeps iterating past malformed records instead of silently dropping or stopping at them.
+    ///
+    /// Records are checked like [`Reader::validate`] does. A bad record is yielded as a
+    /// [`CsvError::MalformedRecord`] holding the line it starts at, and reading resumes at the
+    /// next record. A read failure from the underlying stream is yielded once and ends the iteration.
+    pub fn resync(mut self) -> impl Iterator<Item = Result<Row>> {
+        let mut expected_count = self.owner.header.as_ref().map(Row::count);
+        let mut failed = false;
+        std::iter::from_fn(move || {
+            if failed {
+                return None;
+            }
+            let line = self.owner.progress.lines + 1;
+            match self.owner.read_record() {
+                Ok(row) => Some(match check_record(&row, &mut expected_count) {
+                    Ok(()) => Ok(row),
+                    Err(e) => Err(CsvError::MalformedRecord(line, Box::new(e))),
+                }),
+                // Nothing left to read
+                Err(CsvError::RecordError) => None,
+                Err(e) => {
+                    failed = true;
+                    Some(Err(e))
+                }
+            }
+        })
+    }
+
+    /// Gives back the [`Reader`] driving this iterator, e.g. to [`Reader::rewind`] it.
+    pub fn into_reader(self) -> Reader<R> {
+        self.owner
     }
 }
 
@@ -243,17 +857,275 @@ impl<R: io::Read> Iterator for Entries<R> {
     type Item = Row;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let delimiter = match self.owner.delimiter {
-            Some(delim) => delim,
-            _ => DEFAULT_DELIM,
+        self.owner.read_record().ok()
+    }
+}
+
+/// A record read along with the header of its reader, see [`Reader::entries_with_headers`].
+#[derive(Debug, Clone)]
+pub struct HeaderedRow {
+    row: Row,
+    header_indexes: Rc<HashMap<String, usize>>,
+}
+
+impl HeaderedRow {
+    /// Attempts to retrieve and cast the field under the given column name.
+    ///
+    /// # Arguments
+    /// `col_name` name of the column being searched.
+    ///
+    /// # Errors
+    /// [`CsvError::InvalidColumn`] if no header has the given name.
+    /// [`CsvError::NotAField`] if the record is too short to hold the column.
+    /// [`CsvError::ConversionError`] if the field cannot be parsed into the type T.
+    pub fn get<T: std::str::FromStr>(&self, col_name: &str) -> Result<T> {
+        match self.header_indexes.get(col_name) {
+            Some(index) => self.row.get(*index),
+            None => Err(CsvError::InvalidColumn(col_name.to_string())),
+        }
+    }
+
+    /// Retrieves the underlying record.
+    pub fn row(&self) -> &Row {
+        &self.row
+    }
+
+    /// Unwraps the underlying record.
+    pub fn into_row(self) -> Row {
+        self.row
+    }
+}
+
+/// Statistics gathered while reading records, see [`tee`].
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ParseReport {
+    /// Number of records read, header excluded.
+    pub records: usize,
+    /// Total number of fields across all records.
+    pub fields: usize,
+    /// Fewest fields found in a single record.
+    pub min_fields: usize,
+    /// Most fields found in a single record.
+    pub max_fields: usize,
+    /// Number of bytes consumed from the input, header included.
+    pub bytes: u64,
+}
+
+/// Copies every record from the reader to the writer while gathering a [`ParseReport`].
+///
+/// The header, if any, is written first. Records are re-encoded by the writer,
+/// so quoting is normalized but field contents are unchanged.
+///
+/// # Errors
+/// If reading from the reader or writing to the writer fails.
+///
+/// # Examples:
+/// ```
+/// use csvlib::{reader::tee, FromStr, Reader, Writer};
+///
+/// let reader = Reader::from_str("Name,Age\nMike,15\n").unwrap();
+/// let mut writer = Writer::from_writer(std::io::sink());
+/// let report = tee(reader, &mut writer).unwrap();
+/// assert_eq!(report.records, 1);
+/// ```
+pub fn tee<R, W>(mut reader: Reader<R>, writer: &mut Writer<W>) -> Result<ParseReport>
+where
+    R: io::Read,
+    W: io::Write,
+{
+    let mut report = ParseReport::default();
+    if let Some(header) = &reader.header {
+        writer.write(header)?;
+    }
+    loop {
+        let row = match reader.read_record() {
+            Ok(row) => row,
+            // Nothing left to read
+            Err(CsvError::RecordError) => break,
+            Err(e) => return Err(e),
+        };
+        writer.write(&row)?;
+
+        let count = row.count();
+        report.min_fields = if report.records == 0 {
+            count
+        } else {
+            report.min_fields.min(count)
+        };
+        report.max_fields = report.max_fields.max(count);
+        report.fields += count;
+        report.records += 1;
+    }
+    report.bytes = reader.progress.bytes;
+    Ok(report)
+}
+
+/// Splits raw CSV data into about `chunks` slices that each start and end on a record boundary.
+///
+/// Line breaks inside quoted fields do not end a record, following the same quoting
+/// rules as [`read_fields`]. The first slice always holds exactly the first record,
+/// so a header can be parsed on its own.
+#[cfg(feature = "rayon")]
+pub(crate) fn split_records(data: &[u8], separator: u8, chunks: usize) -> Vec<&[u8]> {
+    let target = data.len() / chunks.max(1) + 1;
+    let mut slices = Vec::new();
+    let mut start = 0;
+    let mut escaping = false;
+    let mut closed = false;
+    let mut field_start = true;
+    let mut index = 0;
+    while index < data.len() {
+        let byte = data[index];
+        index += 1;
+        if byte == QUOTE && (field_start || escaping || closed) {
+            // Opening, closing and doubled quotes toggle escaping, other quotes are literal
+            escaping = !escaping;
+            closed = !escaping;
+            field_start = false;
+            continue;
+        }
+        closed = false;
+        if escaping {
+            continue;
+        } else if byte == separator {
+            field_start = true;
+        } else if byte == CR || byte == LF {
+            if byte == CR && data.get(index) == Some(&LF) {
+                index += 1;
+            }
+            field_start = true;
+            if slices.is_empty() || index - start >= target {
+                slices.push(&data[start..index]);
+                start = index;
+            }
+        } else {
+            field_start = false;
+        }
+    }
+    if start < data.len() {
+        slices.push(&data[start..]);
+    }
+    slices
+}
+
+/// Counts the records of a stream, header included, following the parser's quote rules.
+fn count_raw_records(reader: &mut impl io::BufRead, separator: u8) -> io::Result<usize> {
+    let mut records = 0;
+    let mut escaping = false;
+    let mut closed = false;
+    let mut field_start = true;
+    let mut pending_cr = false;
+    let mut has_content = false;
+    loop {
+        let available = match reader.fill_buf() {
+            Ok(available) => available,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
         };
-        read_fields(
-            &mut self.owner.reader,
-            delimiter,
-            &mut self.field_buffer,
-            &mut self.line_buffer,
-        )
-        .ok()
+        if available.is_empty() {
+            break;
+        }
+        for &byte in available {
+            if pending_cr {
+                pending_cr = false;
+                if byte == LF {
+                    // Second half of a CRLF terminator
+                    continue;
+                }
+            }
+            has_content = true;
+            if byte == QUOTE && (field_start || escaping || closed) {
+                // Opening, closing and doubled quotes toggle escaping, other quotes are literal
+                escaping = !escaping;
+                closed = !escaping;
+                field_start = false;
+                continue;
+            }
+            closed = false;
+            if escaping {
+                continue;
+            } else if byte == separator {
+                field_start = true;
+            } else if byte == CR || byte == LF {
+                records += 1;
+                has_content = false;
+                field_start = true;
+                pending_cr = byte == CR;
+            } else {
+                field_start = false;
+            }
+        }
+        let used = available.len();
+        reader.consume(used);
+    }
+    // The last record may not end with a terminator
+    if has_content {
+        records += 1;
+    }
+    Ok(records)
+}
+
+/// Delimiters considered when detecting the delimiter of some data.
+const DELIMITER_CANDIDATES: [char; 4] = [',', ';', '\t', '|'];
+
+/// Most lines used to detect a delimiter.
+const SNIFF_LINES: usize = 10;
+
+/// Bytes read from a file to detect its delimiter.
+const SNIFF_SAMPLE_SIZE: u64 = 8 * 1024;
+
+/// Picks the candidate delimiter found most often, and the same number of times, on the first lines of `sample`.
+///
+/// Falls back to the most frequent candidate of the first line when no candidate is consistent.
+fn detect_delimiter(sample: &[u8]) -> Option<char> {
+    let mut lines: Vec<[usize; DELIMITER_CANDIDATES.len()]> = Vec::new();
+    let mut counts = [0; DELIMITER_CANDIDATES.len()];
+    let mut escaping = false;
+    for byte in sample {
+        if *byte == QUOTE {
+            escaping = !escaping;
+        } else if escaping {
+            continue;
+        } else if *byte == CR || *byte == LF {
+            // Skip blank lines, including the gap between CR and LF
+            if counts.iter().any(|count| *count > 0) || lines.is_empty() {
+                lines.push(counts);
+            }
+            counts = [0; DELIMITER_CANDIDATES.len()];
+            if lines.len() == SNIFF_LINES {
+                break;
+            }
+        } else if let Some(index) = DELIMITER_CANDIDATES.iter().position(|c| *c as u8 == *byte) {
+            counts[index] += 1;
+        }
+    }
+    // A trailing line may have been cut short, it only counts when it is the only one
+    if lines.is_empty() {
+        lines.push(counts);
+    }
+
+    let first = lines.first()?;
+    let consistent = (0..DELIMITER_CANDIDATES.len())
+        .filter(|index| first[*index] > 0 && lines.iter().all(|line| line[*index] == first[*index]))
+        .max_by_key(|index| first[*index]);
+    let index = consistent.or_else(|| {
+        (0..DELIMITER_CANDIDATES.len())
+            .filter(|index| first[*index] > 0)
+            .max_by_key(|index| first[*index])
+    })?;
+    Some(DELIMITER_CANDIDATES[index])
+}
+
+/// Checks that a record has the expected number of fields and only valid UTF-8.
+///
+/// The first record checked sets the expected count when none is known yet.
+fn check_record(row: &Row, expected_count: &mut Option<usize>) -> Result<()> {
+    if row.count() != *expected_count.get_or_insert(row.count()) {
+        Err(CsvError::RecordError)
+    } else if row.iter().any(|field| field.try_to_string().is_err()) {
+        Err(CsvError::InvalidString)
+    } else {
+        Ok(())
     }
 }
 
@@ -262,77 +1134,304 @@ impl<R: io::Read> Iterator for Entries<R> {
 ///
 /// # Arguments:
 /// `reader` std::io::Read to get data from
-/// `separator' character delimiter for CSV files
+/// `separator' delimiter for CSV files, one or several bytes long
+/// `options` parsing options applied while assembling the row
+/// `row` row the fields are parsed into, previous contents are discarded
+/// `progress` running count of bytes and lines consumed from the reader
 fn read_fields(
     reader: &mut impl io::BufRead,
-    separator: char,
+    separator: &[u8],
+    options: &ParseOptions,
+    row: &mut Row,
     field_buffer: &mut Vec<u8>,
     line_buffer: &mut Vec<u8>,
-) -> Result<Row> {
-    let mut row = Row::with_capacity(line_buffer.capacity());
+    progress: &mut Progress,
+) -> Result<()> {
+    row.inner.clear();
+    row.ranges.clear();
+    row.nulls.clear();
     let mut multi_line = true;
-    let mut quote_first_char = false;
-    let mut current_char: u8 = 0;
+    let mut column = 0;
+
+    // Quoting state is kept across lines so quoted fields may span several of them.
+    // Quotes are only special at the start of a field, anywhere else they are literal.
+    let mut field_start = true;
+    let mut escaping = false;
+    // Whether the previous character closed a quoted part of the field
+    let mut closed = false;
+    // Whether the current field started with a quote, quoted fields are never null
+    let mut quoted = false;
+    let mut quote_run = 0;
+    let quote = options.quote.map_or(QUOTE, |quote| quote as u8);
+    // Line the record starts at, reported when it breaks one of the limits
+    let line = progress.lines + 1;
+    field_buffer.clear();
 
     while multi_line {
         multi_line = false;
         line_buffer.clear();
-        match reader.read_until(b'\n', line_buffer) {
-            Ok(0) => return Err(CsvError::RecordError),
-            Ok(_n) => {
-                let mut escaping = false;
-
+        let read = match &options.record_separator {
+            Some(terminator) => read_until_terminator(reader, terminator, line_buffer),
+            None => read_line(reader, line_buffer),
+        };
+        match read {
+            // The input ended inside a quoted field
+            Ok(0) if escaping => {
+                if options.strict_quotes {
+                    return Err(CsvError::UnterminatedQuote(
+                        String::from_utf8_lossy(field_buffer).to_string(),
+                    ));
+                }
+                push_field(
+                    row,
+                    field_buffer,
+                    std::mem::take(&mut quoted),
+                    &mut column,
+                    options,
+                );
                 field_buffer.clear();
-                let mut quote_count = 0;
-                for c in line_buffer.iter() {
-                    current_char = *c;
-                    if current_char == QUOTE {
-                        quote_count += 1;
-                        if field_buffer.is_empty() {
-                            quote_first_char = true;
+            }
+            Ok(0) => return Err(CsvError::RecordError),
+            Ok(n) => {
+                progress.bytes += n as u64;
+                progress.lines += 1;
+                let mut index = 0;
+                while index < line_buffer.len() {
+                    let current_char = line_buffer[index];
+                    let is_separator = line_buffer[index..].starts_with(separator);
+                    let is_terminator = match &options.record_separator {
+                        Some(terminator) => line_buffer[index..].starts_with(terminator),
+                        None => current_char == CR || current_char == LF,
+                    };
+                    index += 1;
+                    if current_char == quote {
+                        quote_run += 1;
+                        if let Some(limit) = options.max_consecutive_quotes {
+                            if quote_run > limit {
+                                return Err(CsvError::TooManyQuotes(line, limit));
+                            }
                         }
+                    } else {
+                        quote_run = 0;
                     }
 
-                    if current_char == QUOTE && quote_first_char {
-                        if quote_count == 1 {
+                    if closed {
+                        closed = false;
+                        if current_char == quote {
+                            // A doubled quote is kept as a literal quote and the field stays quoted
+                            field_buffer.push(quote);
                             escaping = true;
                             continue;
-                        } else if quote_count > 1 && quote_count % 2 == 0 {
-                            escaping = false;
-                            continue;
+                        } else if !is_separator && !is_terminator {
+                            // Text right after a closing quote, keep the stray quote literally
+                            field_buffer.push(quote);
                         }
-                    } else if current_char == separator as u8 {
+                    }
+
+                    if current_char == quote && field_start {
+                        field_start = false;
+                        escaping = true;
+                        quoted = true;
+                        continue;
+                    } else if current_char == quote && escaping {
+                        escaping = false;
+                        closed = true;
+                        continue;
+                    } else if is_separator {
                         if !escaping {
-                            quote_first_char = false;
-                            row.add_bytes(field_buffer);
+                            field_start = true;
+                            push_field(
+                                row,
+                                field_buffer,
+                                std::mem::take(&mut quoted),
+                                &mut column,
+                                options,
+                            );
                             field_buffer.clear();
-                            quote_count = 0;
+                            // Skip the rest of a separator made of several bytes
+                            index += separator.len() - 1;
                             continue;
                         }
-                    } else if current_char == CR {
-                        continue;
-                    } else if current_char == LF {
+                    } else if is_terminator {
                         if !escaping {
-                            row.add_bytes(field_buffer);
+                            push_field(
+                                row,
+                                field_buffer,
+                                std::mem::take(&mut quoted),
+                                &mut column,
+                                options,
+                            );
                             field_buffer.clear();
-                            return Ok(row);
-                        } else {
-                            multi_line = true;
+                            return check_record_size(row, field_buffer, options, line);
+                        }
+                        multi_line = true;
+                        if current_char == CR && line_buffer.last() == Some(&LF) {
+                            // Quoted CRLF line breaks are kept as a single LF
+                            continue;
                         }
                     }
 
+                    field_start = false;
                     field_buffer.push(current_char);
                 }
 
-                // got to the end and but did not find  a carriage return
-                if !field_buffer.is_empty() || current_char == separator as u8 {
-                    row.add_bytes(field_buffer);
+                check_record_size(row, field_buffer, options, line)?;
+
+                // A line without a terminator ends the input, let the next read handle an open quote
+                if escaping {
+                    multi_line = true;
+                }
+
+                // The input ended without a line terminator. The line was not empty, so there is
+                // always a last field to push, even an empty one after a trailing delimiter
+                if !multi_line {
+                    push_field(
+                        row,
+                        field_buffer,
+                        std::mem::take(&mut quoted),
+                        &mut column,
+                        options,
+                    );
                     field_buffer.clear();
                 }
             }
-            Err(_) => return Err(CsvError::ReadError),
+            Err(e) => return Err(CsvError::ReadError(Some(IoSource(e)))),
+        }
+    }
+
+    Ok(())
+}
+
+/// Fails with [`CsvError::RecordTooLarge`] once a record being assembled exceeds the size limit.
+fn check_record_size(
+    row: &Row,
+    field_buffer: &[u8],
+    options: &ParseOptions,
+    line: usize,
+) -> Result<()> {
+    match options.max_record_size {
+        Some(limit) if row.inner.len() + field_buffer.len() > limit => {
+            Err(CsvError::RecordTooLarge(line, limit))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Adds a parsed field to the row, unless the options leave its column out.
+///
+/// Unquoted fields matching a null value are added as null fields.
+fn push_field(
+    row: &mut Row,
+    field: &[u8],
+    quoted: bool,
+    column: &mut usize,
+    options: &ParseOptions,
+) {
+    let keep = if options.skip_first_column && *column == 0 {
+        false
+    } else {
+        match &options.projection {
+            Some(columns) => columns.contains(column),
+            None => true,
+        }
+    };
+    if keep {
+        if !quoted && options.null_values.iter().any(|null| null == field) {
+            row.add_null();
+        } else {
+            #[cfg(feature = "encoding")]
+            let field = &*decode_field(field, options.encoding);
+            row.add_bytes(field);
+        }
+    }
+    *column += 1;
+}
+
+/// Transcodes a field to UTF-8, borrowing it when no conversion is needed.
+#[cfg(feature = "encoding")]
+fn decode_field(field: &[u8], encoding: Encoding) -> std::borrow::Cow<'_, [u8]> {
+    match encoding {
+        Encoding::Utf8 => std::borrow::Cow::Borrowed(field),
+        Encoding::Latin1 => {
+            let decoded: String = field.iter().map(|byte| *byte as char).collect();
+            std::borrow::Cow::Owned(decoded.into_bytes())
+        }
+    }
+}
+
+/// Reads up to and including the next occurrence of `terminator`, or to the end of the input.
+///
+/// # Returns
+/// The number of bytes read, 0 once the input is exhausted.
+fn read_until_terminator(
+    reader: &mut impl io::BufRead,
+    terminator: &[u8],
+    line_buffer: &mut Vec<u8>,
+) -> io::Result<usize> {
+    let mut read = 0;
+    // A multi-byte terminator is only complete once its last byte follows the others
+    let last = terminator[terminator.len() - 1];
+    loop {
+        let n = reader.read_until(last, line_buffer)?;
+        read += n;
+        if n == 0 || line_buffer.ends_with(terminator) {
+            return Ok(read);
         }
     }
+}
 
-    Ok(row)
+#[doc(hidden)]
+/// Internal function reading a single line, accepting `\n`, `\r\n` and lone `\r` as line endings.
+/// The line ending is kept in the buffer.
+///
+/// # Returns
+/// The number of bytes consumed from the reader, zero at the end of the stream.
+fn read_line(reader: &mut impl io::BufRead, line_buffer: &mut Vec<u8>) -> io::Result<usize> {
+    let mut read = 0;
+    let mut pending_cr = false;
+    loop {
+        let available = match reader.fill_buf() {
+            Ok(available) => available,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        };
+        if available.is_empty() {
+            return Ok(read);
+        }
+
+        let (used, done) = if pending_cr {
+            // A CR ended the previous chunk, only take the next byte if it completes a CRLF
+            if available[0] == LF {
+                line_buffer.push(LF);
+                (1, true)
+            } else {
+                (0, true)
+            }
+        } else {
+            match available.iter().position(|c| *c == LF || *c == CR) {
+                Some(i) if available[i] == CR && i + 1 == available.len() => {
+                    line_buffer.extend_from_slice(available);
+                    pending_cr = true;
+                    (available.len(), false)
+                }
+                Some(i) if available[i] == CR && available[i + 1] == LF => {
+                    line_buffer.extend_from_slice(&available[..i + 2]);
+                    (i + 2, true)
+                }
+                Some(i) => {
+                    line_buffer.extend_from_slice(&available[..=i]);
+                    (i + 1, true)
+                }
+                None => {
+                    line_buffer.extend_from_slice(available);
+                    (available.len(), false)
+                }
+            }
+        };
+        reader.consume(used);
+        read += used;
+        if done {
+            return Ok(read);
+        }
+    }
 }
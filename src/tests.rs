@@ -1,4 +1,9 @@
-use csvlib::{reader::Reader, Row};
+use csvlib::{
+    csv,
+    doc::DocEntry,
+    reader::{tee, ParseReport, Reader},
+    CsvError, Document, Field, FromStr, Row, RowBuilder, Writer,
+};
 
 #[test]
 fn test_well_formed_csv_no_commas_no_quotes() {
@@ -118,3 +123,2165 @@ fn test_csv_row_replace() {
     assert_eq!(row.get::<String>(1).unwrap(), "nameless person");
     assert_eq!(row.get::<String>(2).unwrap(), "partner.");
 }
+
+#[test]
+fn test_writer_unix_terminator() {
+    let mut output = Vec::new();
+    {
+        let mut writer = Writer::from_writer(&mut output).with_terminator(b"\n");
+        writer.write(&csv!["a", "b", "c"]).unwrap();
+        writer.write(&csv![1, 2, 3]).unwrap();
+    }
+    assert!(!output.contains(&b'\r'));
+    assert_eq!(output, b"a,b,c\n1,2,3\n");
+}
+
+fn temp_file(name: &str, contents: &[u8]) -> std::path::PathBuf {
+    let path = std::env::temp_dir().join(format!("csvlib_{}_{name}", std::process::id()));
+    std::fs::write(&path, contents).expect("could not write temporary file");
+    path
+}
+
+#[test]
+fn test_document_from_path_lossy_skips_malformed() {
+    let path = temp_file(
+        "lossy.csv",
+        b"Name,Age\nMike,15\nbroken record with,too,many,fields\nJenny,16\n",
+    );
+    let doc = Document::from_path_lossy(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(doc.count(), 2);
+    assert_eq!(doc.load_errors(), 1);
+    assert_eq!(doc.get_column::<u32>("Age").unwrap(), vec![15, 16]);
+}
+
+#[test]
+fn test_document_from_path_lossy_reads_past_every_bad_record() {
+    let path = temp_file(
+        "lossy_tail.csv",
+        b"Name,Age\nMike,15\nshort\nJenny,16\nbad\xff,17\nMaria,18\ntoo,many,fields\nPaul,19\n",
+    );
+    let doc = Document::from_path_lossy(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(doc.load_errors(), 3);
+    assert_eq!(doc.get_column::<u32>("Age").unwrap(), vec![15, 16, 18, 19]);
+}
+
+#[cfg(feature = "gzip")]
+#[test]
+fn test_reader_from_gzip_path() {
+    use std::io::Write;
+
+    let data = b"header1,header2\nr1c1,r1c2\n\"r2,c1\",r2c2\n";
+    let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+    encoder.write_all(data).unwrap();
+    let path = temp_file("gzip.csv.gz", &encoder.finish().unwrap());
+
+    let reader = Reader::from_gzip_path(&path).unwrap();
+    let header = reader.headers().unwrap();
+    let rows: Vec<_> = reader.entries().collect();
+    std::fs::remove_file(&path).unwrap();
+
+    let plain = Reader::from_str(std::str::from_utf8(data).unwrap()).unwrap();
+    assert_eq!(Some(header), plain.headers());
+    assert_eq!(rows, plain.entries().collect::<Vec<_>>());
+}
+
+#[test]
+fn test_writer_quotes_null_sentinels() {
+    let mut output = Vec::new();
+    {
+        let mut writer = Writer::from_writer(&mut output)
+            .with_terminator(b"\n")
+            .with_null_values(&["NA"]);
+        writer.write(&csv!["NA", "NAN", "", "value"]).unwrap();
+    }
+    assert_eq!(output, b"\"NA\",NAN,,value\n");
+}
+
+#[test]
+fn test_null_values_round_trip() {
+    let mut row = csv!["NA"];
+    row.add_null();
+    row.add("value");
+
+    let mut output = Vec::new();
+    {
+        let mut writer = Writer::from_writer(&mut output)
+            .with_terminator(b"\n")
+            .with_null_values(&["NA"]);
+        writer.write(&row).unwrap();
+    }
+    assert_eq!(output, b"\"NA\",NA,value\n");
+
+    let reader = Reader::builder()
+        .with_reader(output.as_slice())
+        .with_null_values(&["NA"])
+        .build()
+        .unwrap();
+    let read = reader.entries().next().unwrap();
+    assert_eq!(read, row);
+    assert_eq!(read.get_opt::<String>(0), Ok(Some("NA".to_string())));
+    assert_eq!(read.get_opt::<String>(1), Ok(None));
+    assert!(read.is_null(1));
+    assert_eq!(read.get_opt::<String>(2), Ok(Some("value".to_string())));
+}
+
+#[test]
+fn test_document_reorder_and_append_keep_nulls() {
+    let read_document = |data: &'static str| {
+        let reader = Reader::builder()
+            .with_reader(data.as_bytes())
+            .with_header(true)
+            .with_null_values(&["NA"])
+            .build()
+            .unwrap();
+        Document::try_from(reader).unwrap()
+    };
+    let mut doc = read_document("a,b\nNA,1\n");
+    doc.reorder_columns(&["b", "a"]).unwrap();
+    doc.append_aligned(&read_document("a,b\n2,NA\n")).unwrap();
+
+    let mut output = Vec::new();
+    {
+        let mut writer = Writer::from_writer(&mut output)
+            .with_terminator(b"\n")
+            .with_null_values(&["NA"]);
+        writer.write(doc.get_rows_where("b", &1)[0]).unwrap();
+        writer.write(doc.get_rows_where("a", &2)[0]).unwrap();
+    }
+    assert_eq!(output, b"1,NA\nNA,2\n");
+}
+
+#[test]
+fn test_row_nulls_follow_edits() {
+    let mut row = csv!["a"];
+    row.add_null();
+    row.add("c");
+    row.insert(0, "start");
+    assert!(row.is_null(2));
+    assert!(row.slice(1..3).is_null(1));
+    row.remove(0);
+    assert!(row.is_null(1));
+    row.replace(1, "b");
+    assert!(!row.is_null(1));
+    assert_eq!(row, csv!["a", "b", "c"]);
+
+    let mut null_row = csv!["a"];
+    null_row.add_null();
+    let empty_row = csv!["a", ""];
+    assert_ne!(null_row, empty_row);
+    assert!(!null_row.content_eq(&empty_row));
+    let mut doc = Document::with_headers(&["x", "y"]);
+    doc.add_row(empty_row);
+    doc.build_row_index();
+    assert!(!doc.contains_row(&null_row));
+}
+
+#[test]
+fn test_reader_rewind() {
+    let data = "header1,header2\nr1c1,r1c2\nr2c1,r2c2\n";
+    let reader = Reader::builder()
+        .with_header(true)
+        .with_reader(std::io::Cursor::new(data))
+        .build()
+        .unwrap();
+
+    let mut entries = reader.entries();
+    let first_pass: Vec<_> = entries.by_ref().collect();
+    let mut reader = entries.into_reader();
+    reader.rewind().unwrap();
+    let second_pass: Vec<_> = reader.entries().collect();
+
+    assert_eq!(first_pass.len(), 2);
+    assert_eq!(first_pass, second_pass);
+}
+
+#[test]
+fn test_reader_rewind_from_stream_offset() {
+    use std::io::{Seek, SeekFrom};
+
+    let mut cursor = std::io::Cursor::new("JUNK\nA,B\n1,2\n3,4\n");
+    cursor.seek(SeekFrom::Start(5)).unwrap();
+    let mut reader = Reader::builder()
+        .with_header(true)
+        .with_reader(cursor)
+        .build()
+        .unwrap();
+    assert_eq!(reader.headers(), Some(csv!["A", "B"]));
+
+    assert_eq!(
+        reader.read_decoded_into(|row| row.clone()),
+        Ok(Some(csv![1, 2]))
+    );
+    reader.rewind().unwrap();
+    let rows: Vec<Row> = reader.entries().collect();
+    assert_eq!(rows, vec![csv![1, 2], csv![3, 4]]);
+}
+
+#[derive(Debug, PartialEq)]
+struct Student {
+    name: String,
+    age: u32,
+}
+
+impl TryFrom<DocEntry<'_>> for Student {
+    type Error = CsvError;
+
+    fn try_from(entry: DocEntry) -> Result<Self, Self::Error> {
+        Ok(Student {
+            name: entry.get_value("Name")?,
+            age: entry.get_value("Age")?,
+        })
+    }
+}
+
+#[test]
+fn test_document_partition_decoded() {
+    let mut doc = Document::with_headers(&["Name", "Age"]);
+    doc.add_row(csv!["Mike", 15]);
+    doc.add_row(csv!["Jenny", "sixteen"]);
+    doc.add_row(csv!["Paul", 17]);
+    doc.add_row(csv!["Anna", -3]);
+
+    let (students, errors) = doc.partition_decoded::<Student>();
+    assert_eq!(
+        students,
+        vec![
+            Student {
+                name: "Mike".to_owned(),
+                age: 15
+            },
+            Student {
+                name: "Paul".to_owned(),
+                age: 17
+            }
+        ]
+    );
+    assert_eq!(errors.len(), 2);
+    assert_eq!(errors[0].0, 1);
+    assert_eq!(errors[1].0, 3);
+    assert!(matches!(errors[0].1, CsvError::ConversionError(1, _)));
+}
+
+#[test]
+fn test_reader_entries_with_position() {
+    let data = "header1,header2\r\nr1c1,r1c2\r\n\"multi\nline\",r2c2\r\nr3c1,r3c2";
+    let reader = Reader::builder()
+        .with_header(true)
+        .with_reader(std::io::Cursor::new(data))
+        .build()
+        .unwrap();
+
+    let positions: Vec<u64> = reader.entries_with_position().map(|(pos, _)| pos).collect();
+    assert_eq!(positions[0], "header1,header2\r\n".len() as u64);
+    assert_eq!(positions[1], data.find("\"multi").unwrap() as u64);
+}
+
+#[test]
+fn test_field_cast_trimmed() {
+    let field = Field::from(" 42 ");
+    assert_eq!(field.cast_trimmed::<i32>().unwrap(), 42);
+    assert_eq!(
+        field.cast::<i32>(),
+        Err(CsvError::FieldParseError("i32".to_owned()))
+    );
+}
+
+#[test]
+fn test_field_try_to_string_invalid_utf8() {
+    let field = Field::new(&[b'o', b'k', 0xFF, 0xFE]);
+    assert_eq!(field.try_to_string(), Err(CsvError::InvalidString));
+    assert_eq!(Field::from("ok").try_to_string().unwrap(), "ok");
+}
+
+#[test]
+fn test_field_cast_bool() {
+    let truthy = ["y", "yes", "1"];
+    let falsy = ["n", "no", "0"];
+    assert!(Field::from("Y").cast_bool(&truthy, &falsy).unwrap());
+    assert!(!Field::from("no").cast_bool(&truthy, &falsy).unwrap());
+    assert!(Field::from("yEs").cast_bool(&truthy, &falsy).unwrap());
+    assert!(!Field::from("0").cast_bool(&truthy, &falsy).unwrap());
+    assert_eq!(
+        Field::from("maybe").cast_bool(&truthy, &falsy),
+        Err(CsvError::FieldParseError("bool".to_owned()))
+    );
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn test_document_par_partition_decoded() {
+    let mut doc = Document::with_headers(&["Name", "Age"]);
+    for i in 0..1000 {
+        if i % 7 == 0 {
+            doc.add_row(csv![format!("Student {i}"), "unknown"]);
+        } else {
+            doc.add_row(csv![format!("Student {i}"), i]);
+        }
+    }
+
+    let (sequential, sequential_errors) = doc.partition_decoded::<Student>();
+    let (parallel, parallel_errors) = doc.par_partition_decoded::<Student>();
+    assert_eq!(parallel, sequential);
+    assert_eq!(parallel_errors, sequential_errors);
+    assert_eq!(parallel_errors.len(), 143);
+}
+
+#[test]
+fn test_csv_row_insert() {
+    let mut row = Row::from(&["b", "d"][..]);
+    row.insert(0, "a");
+    row.insert(2, 'c');
+    row.insert(4, 5);
+    row.insert(10, "ignored");
+
+    assert_eq!(row.count(), 5);
+    let fields: Vec<String> = (0..row.count())
+        .map(|i| row.get::<String>(i).unwrap())
+        .collect();
+    assert_eq!(fields, vec!["a", "b", "c", "d", "5"]);
+    assert_eq!(row, csv!["a", "b", "c", "d", 5]);
+}
+
+#[test]
+fn test_header_with_quoted_newline() {
+    let data = "\"Full\nName\",Age\r\nJohn Doe,30\r\n";
+    let reader = Reader::builder()
+        .with_header(true)
+        .with_reader(std::io::Cursor::new(data))
+        .build()
+        .unwrap();
+
+    let header = reader.headers().unwrap();
+    assert_eq!(header.count(), 2);
+    assert_eq!(header.get::<String>(0).unwrap(), "Full\nName");
+    assert_eq!(header.get::<String>(1).unwrap(), "Age");
+
+    let rows: Vec<_> = reader.entries().collect();
+    assert_eq!(rows, vec![csv!["John Doe", 30]]);
+}
+
+#[test]
+fn test_csv_row_from_owned_strings() {
+    let fields: Vec<String> = (1..=3).map(|i| format!("field {i}")).collect();
+    let row = Row::from(fields.clone());
+    assert_eq!(row.count(), 3);
+    assert_eq!(row.get::<String>(0).unwrap(), "field 1");
+    assert_eq!(row.get::<String>(2).unwrap(), "field 3");
+
+    let collected: Row = fields.into_iter().collect();
+    assert_eq!(collected, row);
+}
+
+#[test]
+fn test_document_to_html_table() {
+    let mut doc = Document::with_headers(&["Name", "Notes"]);
+    doc.add_row(csv!["Mike", "<b>bold</b> & more"]);
+
+    let html = doc.to_html_table();
+    assert_eq!(
+        html,
+        "<table>\n\
+         <thead>\n<tr><th>Name</th><th>Notes</th></tr>\n</thead>\n\
+         <tbody>\n<tr><td>Mike</td><td>&lt;b&gt;bold&lt;/b&gt; &amp; more</td></tr>\n</tbody>\n\
+         </table>\n"
+    );
+}
+
+#[test]
+fn test_csv_row_map_fields() {
+    let mut row = Row::from(&["hi", "there", "", "partner."][..]);
+    row.map_fields(|field| field.to_uppercase());
+
+    assert_eq!(row.count(), 4);
+    assert_eq!(row.get_range(0), Some(&b"HI"[..]));
+    assert_eq!(row.get_range(1), Some(&b"THERE"[..]));
+    assert_eq!(row.get_range(2), Some(&b""[..]));
+    assert_eq!(row.get_range(3), Some(&b"PARTNER."[..]));
+    assert_eq!(row, csv!["HI", "THERE", "", "PARTNER."]);
+}
+
+#[test]
+fn test_mixed_line_endings() {
+    let data =
+        "header1,header2\nr1c1,r1c2\r\nr2c1,r2c2\rr3c1,\"r3\rc2\"\r\"r4\r\nc1\",r4c2\nr5c1,r5c2";
+    let reader = Reader::builder()
+        .with_header(true)
+        .with_reader(std::io::Cursor::new(data))
+        .build()
+        .unwrap();
+
+    assert_eq!(reader.headers().unwrap(), csv!["header1", "header2"]);
+    let rows: Vec<_> = reader.entries().collect();
+    assert_eq!(
+        rows,
+        vec![
+            csv!["r1c1", "r1c2"],
+            csv!["r2c1", "r2c2"],
+            csv!["r3c1", "r3\rc2"],
+            csv!["r4\nc1", "r4c2"],
+            csv!["r5c1", "r5c2"],
+        ]
+    );
+}
+
+#[test]
+fn test_csv_row_slice() {
+    let row = Row::from(&["a", "b", "c", "d"][..]);
+    let sliced = row.slice(1..3);
+    assert_eq!(sliced.count(), 2);
+    assert_eq!(sliced.get::<String>(0).unwrap(), "b");
+    assert_eq!(sliced.get::<String>(1).unwrap(), "c");
+
+    assert_eq!(row.slice(2..10), csv!["c", "d"]);
+    assert_eq!(row.slice(7..10).count(), 0);
+}
+
+#[test]
+fn test_document_format_table() {
+    let mut doc = Document::with_headers(&["Name", "Email"]);
+    doc.add_row(csv!["Mike", "kime@mail.com"]);
+    doc.add_row(csv!["Jennifer", "a.very.long.address@mail.com"]);
+
+    let table = doc.format_table(12);
+    let lines: Vec<&str> = table.lines().collect();
+    assert_eq!(
+        lines,
+        vec![
+            "+----------+--------------+",
+            "| Name     | Email        |",
+            "+----------+--------------+",
+            "| Mike     | kime@mail.c… |",
+            "| Jennifer | a.very.long… |",
+            "+----------+--------------+",
+        ]
+    );
+    assert!(lines
+        .iter()
+        .all(|line| line.chars().count() == lines[0].len()));
+}
+
+#[test]
+fn test_error_source_on_file_open_failure() {
+    use std::error::Error;
+
+    let err = Reader::from_path("./this/file/does/not/exist.csv").unwrap_err();
+    assert!(matches!(err, CsvError::FileError(Some(_))));
+    assert!(err.source().is_some());
+    assert_eq!(err.to_string(), "Error accessing file.");
+}
+
+#[test]
+fn test_reader_projection() {
+    let data = "header1,header2,header3,header4\nr1c1,r1c2,r1c3,r1c4\nr2c1,\"r2,c2\",r2c3,r2c4\n";
+    let reader = Reader::builder()
+        .with_header(true)
+        .with_projection(vec![0, 3])
+        .with_reader(std::io::Cursor::new(data))
+        .build()
+        .unwrap();
+
+    assert_eq!(reader.headers().unwrap(), csv!["header1", "header4"]);
+    let rows: Vec<_> = reader.entries().collect();
+    assert_eq!(rows, vec![csv!["r1c1", "r1c4"], csv!["r2c1", "r2c4"]]);
+}
+
+#[test]
+fn test_reader_validate() {
+    let data = "header1,header2\nr1c1,r1c2\n\"r2\nc1\",r2c2,extra\nr3c1,r3c2\n";
+    let reader = Reader::builder()
+        .with_header(true)
+        .with_reader(std::io::Cursor::new(data))
+        .build()
+        .unwrap();
+
+    let (rows, errors) = reader.validate();
+    assert_eq!(rows.len(), 2);
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0], (3, CsvError::RecordError));
+    assert_eq!(rows[1], csv!["r3c1", "r3c2"]);
+}
+
+#[test]
+fn test_reader_validate_continues_after_rejected_record() {
+    let data = format!(
+        "Name,Notes\nAnn,ok\nBob,{}\nCid,fine\nshort\nDan,also fine\n",
+        "x".repeat(100)
+    );
+    let reader = Reader::builder()
+        .with_reader(data.as_bytes())
+        .with_header(true)
+        .with_max_record_size(64)
+        .build()
+        .unwrap();
+
+    let (rows, errors) = reader.validate();
+    assert_eq!(
+        rows,
+        vec![
+            csv!["Ann", "ok"],
+            csv!["Cid", "fine"],
+            csv!["Dan", "also fine"]
+        ]
+    );
+    assert_eq!(
+        errors,
+        vec![
+            (3, CsvError::RecordTooLarge(3, 64)),
+            (5, CsvError::RecordError)
+        ]
+    );
+}
+
+#[test]
+fn test_field_numeric_equality() {
+    let row = csv![42, 2.5, "forty two"];
+    let mut fields = row.iter();
+    assert_eq!(fields.next().unwrap(), 42);
+    assert_eq!(fields.next().unwrap(), 2.5);
+    assert_ne!(fields.next().unwrap(), 42);
+
+    assert_ne!(Field::from("41"), 42);
+    assert_eq!(Field::from("42"), 42.0);
+    assert_ne!(Field::from("forty two"), 42.0);
+}
+
+#[test]
+fn test_document_dedup() {
+    let mut doc = Document::with_headers(&["Name", "Email"]);
+    doc.add_row(csv!["Mike", "mike@mail.com"]);
+    doc.add_row(csv!["Jenny", "jenny@mail.com"]);
+    doc.add_row(csv!["Mike", "mike@mail.com"]);
+    doc.add_row(csv!["Mik", "emike@mail.com"]);
+    doc.add_row(csv!["Jenny", "jenny@mail.com"]);
+
+    doc.dedup();
+    assert_eq!(
+        doc.get_column::<String>("Name").unwrap(),
+        vec!["Mike", "Jenny", "Mik"]
+    );
+}
+
+#[test]
+fn test_document_dedup_by_column() {
+    let mut doc = Document::with_headers(&["Name", "Email"]);
+    doc.add_row(csv!["Mike", "mike@mail.com"]);
+    doc.add_row(csv!["Jenny", "jenny@mail.com"]);
+    doc.add_row(csv!["Michael", "mike@mail.com"]);
+    doc.add_row(csv!["Paul", "paul@mail.com"]);
+    doc.add_row(csv!["Jen", "jenny@mail.com"]);
+
+    doc.dedup_by_column("Email").unwrap();
+    assert_eq!(
+        doc.get_column::<String>("Name").unwrap(),
+        vec!["Mike", "Jenny", "Paul"]
+    );
+    assert_eq!(
+        doc.dedup_by_column("Phone"),
+        Err(CsvError::InvalidColumn("Phone".to_owned()))
+    );
+}
+
+#[test]
+fn test_document_write_partitioned() {
+    let dir = std::env::temp_dir().join(format!("csvlib_{}_partitions", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+
+    let mut doc = Document::with_headers(&["Region", "Sales"]);
+    doc.add_row(csv!["North", 10]);
+    doc.add_row(csv!["South", 20]);
+    doc.add_row(csv!["North", 30]);
+    doc.add_row(csv!["East/West", 40]);
+
+    let paths = doc.write_partitioned(&dir, "Region").unwrap();
+    assert_eq!(
+        paths,
+        vec![
+            dir.join("North.csv"),
+            dir.join("South.csv"),
+            dir.join("East_West.csv")
+        ]
+    );
+
+    let north = Document::from_path(&paths[0]).unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert_eq!(north.get_headers_row(), csv!["Region", "Sales"]);
+    assert_eq!(north.get_column::<u32>("Sales").unwrap(), vec![10, 30]);
+}
+
+#[test]
+fn test_document_duplicates_by_column() {
+    let mut doc = Document::with_headers(&["Name", "Email"]);
+    doc.add_row(csv!["Mike", "mike@mail.com"]);
+    doc.add_row(csv!["Jenny", "jenny@mail.com"]);
+    doc.add_row(csv!["Michael", "mike@mail.com"]);
+    doc.add_row(csv!["Paul", "paul@mail.com"]);
+    doc.add_row(csv!["Jen", "jenny@mail.com"]);
+
+    let duplicates = doc.duplicates_by_column("Email").unwrap();
+    let names: Vec<String> = duplicates
+        .iter()
+        .map(|entry| entry.get_value("Name").unwrap())
+        .collect();
+    assert_eq!(names, vec!["Mike", "Jenny", "Michael", "Jen"]);
+
+    assert!(doc.duplicates_by_column("Name").unwrap().is_empty());
+    assert!(matches!(
+        doc.duplicates_by_column("Phone"),
+        Err(CsvError::InvalidColumn(_))
+    ));
+}
+
+#[test]
+fn test_reader_read_decoded_into() {
+    let data = "Item,Price\nApple,2\nPear,3\n\"Melon, large\",10\n";
+    let mut reader = Reader::from_str(data).unwrap();
+    let mut total = 0;
+    while let Some(price) = reader
+        .read_decoded_into(|row| row.get::<u32>(1).unwrap())
+        .unwrap()
+    {
+        total += price;
+    }
+
+    let expected: u32 = Reader::from_str(data)
+        .unwrap()
+        .entries()
+        .map(|row| row.get::<u32>(1).unwrap())
+        .sum();
+    assert_eq!(total, expected);
+    assert_eq!(total, 15);
+}
+
+#[test]
+fn test_writer_with_headers_written_once() {
+    let mut output = Vec::new();
+    {
+        let mut writer = Writer::from_writer(&mut output)
+            .with_terminator(b"\n")
+            .with_headers(&csv!["Name", "Age"]);
+        writer.write(&csv!["Mike", 15]).unwrap();
+        writer
+            .write_all(&[csv!["Jenny", 16], csv!["Paul", 17]])
+            .unwrap();
+    }
+    assert_eq!(
+        String::from_utf8(output).unwrap(),
+        "Name,Age\nMike,15\nJenny,16\nPaul,17\n"
+    );
+}
+
+#[test]
+fn test_document_fill_down() {
+    let mut doc = Document::with_headers(&["Category", "Item"]);
+    doc.add_row(csv!["", "Loose change"]);
+    doc.add_row(csv!["Fruit", "Apple"]);
+    doc.add_row(csv!["", "Pear"]);
+    doc.add_row(csv!["", "Melon"]);
+    doc.add_row(csv!["Vegetable", "Carrot"]);
+    doc.add_row(csv!["", "Leek"]);
+
+    doc.fill_down("Category").unwrap();
+    assert_eq!(
+        doc.get_column::<String>("Category").unwrap(),
+        vec!["", "Fruit", "Fruit", "Fruit", "Vegetable", "Vegetable"]
+    );
+    assert_eq!(doc.get_column::<String>("Item").unwrap()[3], "Melon");
+    assert!(doc.fill_down("Price").is_err());
+}
+
+#[test]
+fn test_document_add_row_checked() {
+    let mut doc = Document::with_headers(&["Name", "Age"]);
+    assert!(doc.add_row_checked(csv!["Mike", 15]).is_ok());
+    assert!(doc.add_row_checked(&["Jenny", "16"][..]).is_ok());
+    assert_eq!(
+        doc.add_row_checked(csv!["Paul", 17, "extra"]),
+        Err(CsvError::RecordError)
+    );
+    assert_eq!(
+        doc.add_row_checked(csv!["Anna"]),
+        Err(CsvError::RecordError)
+    );
+    assert_eq!(doc.count(), 2);
+
+    let mut headerless = Document::empty();
+    assert!(headerless.add_row_checked(csv!["any", "width"]).is_ok());
+}
+
+#[test]
+fn test_reader_tee() {
+    let data = "Name,Notes\r\nMike,\"likes, commas\"\r\nJenny,\"says \"\"hi\"\"\"\r\nPaul,\r\n";
+    let reader = Reader::from_str(data).unwrap();
+    let mut output = Vec::new();
+    let report = {
+        let mut writer = Writer::from_writer(&mut output);
+        tee(reader, &mut writer).unwrap()
+    };
+
+    assert_eq!(
+        report,
+        ParseReport {
+            records: 3,
+            fields: 6,
+            min_fields: 2,
+            max_fields: 2,
+            bytes: data.len() as u64,
+        }
+    );
+    assert_eq!(String::from_utf8(output).unwrap(), data);
+}
+
+#[test]
+fn test_field_framed_round_trip() {
+    let fields = [
+        Field::from("plain"),
+        Field::from(""),
+        Field::from("añejo ☕"),
+    ];
+    let mut stream = Vec::new();
+    for field in &fields {
+        stream.extend(field.encode_framed().unwrap());
+    }
+
+    let mut decoded = Vec::new();
+    let mut offset = 0;
+    while offset < stream.len() {
+        let (field, used) = Field::decode_framed(&stream[offset..]).unwrap();
+        decoded.push(field);
+        offset += used;
+    }
+    assert_eq!(decoded, fields);
+    assert_eq!(Field::from("").encode_framed(), Ok(vec![0, 0, 0, 0]));
+
+    assert!(Field::decode_framed(&[5, 0, 0]).is_err());
+    assert!(Field::decode_framed(&[5, 0, 0, 0, b'a']).is_err());
+    assert!(Field::decode_framed(&[0xff, 0xff, 0xff, 0xff, b'a']).is_err());
+}
+
+#[test]
+fn test_document_write_to_buffer() {
+    let mut doc = Document::with_headers(&["Name", "Notes"]);
+    doc.add_row(csv!["Mike", "likes, commas"]);
+    doc.add_row(csv!["Jenny", "says \"hi\""]);
+
+    let mut output = Vec::new();
+    doc.write_to(&mut output).unwrap();
+
+    let reader = Reader::from_str(&String::from_utf8(output).unwrap()).unwrap();
+    assert_eq!(reader.headers().unwrap(), csv!["Name", "Notes"]);
+    let rows: Vec<_> = reader.entries().collect();
+    assert_eq!(
+        rows,
+        vec![csv!["Mike", "likes, commas"], csv!["Jenny", "says \"hi\""]]
+    );
+}
+
+#[test]
+fn test_document_shape() {
+    let mut doc = Document::with_headers(&["Name", "Age", "Email"]);
+    assert_eq!(doc.shape(), (0, 3));
+    doc.add_row(csv!["Mike", 15, "kime@mail.com"]);
+    doc.add_row(csv!["Jenny", 16, "jeng@mail.com"]);
+
+    assert_eq!(doc.row_count(), 2);
+    assert_eq!(doc.column_count(), 3);
+    assert_eq!(doc.shape(), (2, 3));
+    assert_eq!(Document::empty().shape(), (0, 0));
+}
+
+#[test]
+fn test_document_case_insensitive_columns() {
+    let mut doc = Document::with_headers(&["Name", "Email"]);
+    doc.add_row(csv!["Mike", "kime@mail.com"]);
+    doc.add_row(csv!["Jenny", "jeng@mail.com"]);
+
+    assert_eq!(
+        doc.get_column_ci::<String>("email").unwrap(),
+        vec!["kime@mail.com", "jeng@mail.com"]
+    );
+    assert!(doc.is_valid_column_ci("EMAIL"));
+    assert!(doc.get_column::<String>("email").is_err());
+
+    assert_eq!(
+        doc.get_column_ci::<String>("phone"),
+        Err(CsvError::InvalidColumn("phone".to_owned()))
+    );
+    assert!(!doc.is_valid_column_ci("phone"));
+
+    let ambiguous = Document::with_headers(&["id", "ID"]);
+    assert_eq!(
+        ambiguous.get_column_ci::<String>("Id"),
+        Err(CsvError::AmbiguousColumn("Id".to_owned()))
+    );
+    assert!(!ambiguous.is_valid_column_ci("Id"));
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn test_reader_entries_json() {
+    let data = "Name,Age,Notes\nMike,15,\"likes, commas\"\nJenny,16,\n";
+    let rows: Vec<_> = Reader::from_str(data).unwrap().entries_json().collect();
+
+    assert_eq!(rows.len(), 2);
+    assert_eq!(
+        rows[0].as_ref().unwrap(),
+        &serde_json::json!({"Name": "Mike", "Age": "15", "Notes": "likes, commas"})
+    );
+    assert_eq!(
+        rows[1].as_ref().unwrap(),
+        &serde_json::json!({"Name": "Jenny", "Age": "16", "Notes": ""})
+    );
+}
+
+#[test]
+fn test_reader_skip_first_column() {
+    let path = temp_file(
+        "indexed.csv",
+        b",Name,Age\n0,Mike,15\n1,Jenny,16\n2,Paul,17\n",
+    );
+    let reader = Reader::builder()
+        .with_header(true)
+        .with_skip_first_column(true)
+        .with_reader(std::fs::File::open(&path).unwrap())
+        .build()
+        .unwrap();
+    let doc = Document::try_from(reader).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(doc.get_headers_row(), csv!["Name", "Age"]);
+    assert_eq!(doc.shape(), (3, 2));
+    assert_eq!(
+        doc.get_column::<String>("Name").unwrap(),
+        vec!["Mike", "Jenny", "Paul"]
+    );
+    assert_eq!(doc.get_value::<u32>(2, "Age").unwrap(), 17);
+}
+
+#[test]
+fn test_csv_row_map_field() {
+    let mut row = Row::from(&["Hi", "there", "partner."][..]);
+    row.map_field(1, |field| format!("{field} again, my"));
+    assert_eq!(row, csv!["Hi", "there again, my", "partner."]);
+
+    row.map_field(1, |_| String::new());
+    assert_eq!(row, csv!["Hi", "", "partner."]);
+
+    row.map_field(5, |_| "ignored".to_string());
+    assert_eq!(row.count(), 3);
+}
+
+#[test]
+fn test_writer_formula_guard() {
+    let mut output = Vec::new();
+    {
+        let mut writer = Writer::from_writer(&mut output)
+            .with_terminator(b"\n")
+            .with_formula_guard(true);
+        writer
+            .write(&csv!["=SUM(A1)", "+1", "@cmd", "plain", "a=b"])
+            .unwrap();
+    }
+    assert_eq!(
+        String::from_utf8(output).unwrap(),
+        "'=SUM(A1),'+1,'@cmd,plain,a=b\n"
+    );
+
+    let mut output = Vec::new();
+    {
+        let mut writer = Writer::from_writer(&mut output)
+            .with_terminator(b"\n")
+            .with_formula_guard_prefix('\t');
+        writer.write(&csv!["-2", "2"]).unwrap();
+    }
+    assert_eq!(String::from_utf8(output).unwrap(), "\t-2,2\n");
+
+    let mut output = Vec::new();
+    {
+        let mut writer = Writer::from_writer(&mut output).with_terminator(b"\n");
+        writer.write(&csv!["=SUM(A1)"]).unwrap();
+    }
+    assert_eq!(String::from_utf8(output).unwrap(), "=SUM(A1)\n");
+}
+
+#[test]
+fn test_document_missing_as_empty() {
+    let path = temp_file(
+        "ragged.csv",
+        b"Name,Age,Email\nMike,15,kime@mail.com\nJenny,16\n",
+    );
+    let strict = Document::from_path(&path).unwrap();
+    let lenient = Document::from_path(&path)
+        .unwrap()
+        .with_missing_as_empty(true);
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(
+        strict.get_value::<String>(1, "Email"),
+        Err(CsvError::NotAField(2))
+    );
+    assert_eq!(lenient.get_value::<String>(1, "Email").unwrap(), "");
+    assert_eq!(
+        lenient.get_column::<String>("Email").unwrap(),
+        vec!["kime@mail.com", ""]
+    );
+    let last = lenient.rows().last().unwrap();
+    assert_eq!(last.get_value::<String>("Email").unwrap(), "");
+    assert_eq!(
+        lenient.get_value_at::<String>(1, 3),
+        Err(CsvError::NotAField(3))
+    );
+}
+
+#[test]
+fn test_doc_entry_get_bytes() {
+    let path = temp_file("bytes.csv", b"Name,Payload\nMike,ab\xff\xfecd\n");
+    let document = Document::from_path(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    let entry = document.rows().next().unwrap();
+    assert_eq!(entry.get_bytes("Payload"), Some(&b"ab\xff\xfecd"[..]));
+    assert_eq!(entry.get_bytes("Name"), Some(&b"Mike"[..]));
+    assert_eq!(entry.get_bytes("Missing"), None);
+}
+
+#[test]
+fn test_field_to_csv_fragment() {
+    assert_eq!(Field::from("plain").to_csv_fragment(','), "plain");
+    assert_eq!(Field::from("a,b").to_csv_fragment(','), "\"a,b\"");
+    assert_eq!(Field::from("a,b").to_csv_fragment(';'), "a,b");
+    assert_eq!(Field::from("a;b").to_csv_fragment(';'), "\"a;b\"");
+    assert_eq!(
+        Field::from("say \"hi\"").to_csv_fragment(','),
+        "\"say \"\"hi\"\"\""
+    );
+}
+
+#[test]
+fn test_strict_str_access() {
+    let field = Field::new(b"ab\xffcd");
+    assert_eq!(field.try_str(), Err(CsvError::InvalidString));
+    assert_eq!(Field::from("café").try_str(), Ok("café"));
+
+    let mut row = Row::new();
+    row.add_bytes(b"ok");
+    row.add_bytes(b"\xc3\x28");
+    assert_eq!(row.get_str(0), Ok("ok"));
+    assert_eq!(row.get_str(1), Err(CsvError::InvalidString));
+    assert_eq!(row.get_str(2), Err(CsvError::NotAField(2)));
+}
+
+struct Person {
+    name: String,
+    age: u32,
+}
+
+impl From<Person> for Row {
+    fn from(person: Person) -> Self {
+        csv![person.name, person.age]
+    }
+}
+
+#[test]
+fn test_document_from_iter_typed() {
+    let people = vec![
+        Person {
+            name: "Mike".to_string(),
+            age: 15,
+        },
+        Person {
+            name: "Jenny".to_string(),
+            age: 16,
+        },
+    ];
+    let document = Document::from_iter_typed(&["Name", "Age"], people);
+    assert_eq!(document.shape(), (2, 2));
+    assert_eq!(document.get_column::<u32>("Age").unwrap(), vec![15, 16]);
+    assert_eq!(document.get_value::<String>(1, "Name").unwrap(), "Jenny");
+}
+
+#[cfg(feature = "encoding")]
+#[test]
+fn test_reader_latin1_encoding() {
+    use csvlib::reader::Encoding;
+
+    let data: &[u8] = b"Name,City\nRen\xe9,Montr\xe9al\n";
+    let reader = Reader::builder()
+        .with_reader(data)
+        .with_header(true)
+        .with_encoding(Encoding::Latin1)
+        .build()
+        .unwrap();
+    let row = reader.entries().next().unwrap();
+    assert_eq!(row.get::<String>(0).unwrap(), "René");
+    assert_eq!(row.get_str(1), Ok("Montréal"));
+
+    let reader = Reader::builder()
+        .with_reader(data)
+        .with_header(true)
+        .build()
+        .unwrap();
+    let row = reader.entries().next().unwrap();
+    assert_eq!(row.get_str(0), Err(CsvError::InvalidString));
+}
+
+#[test]
+fn test_entries_resync() {
+    let data: &[u8] = b"Name,Age\nMike,15\nbroken\nJenny,16\nAl,\xff\n";
+    let reader = Reader::builder()
+        .with_reader(data)
+        .with_header(true)
+        .build()
+        .unwrap();
+    let results: Vec<_> = reader.entries().resync().collect();
+    assert_eq!(results.len(), 4);
+    assert_eq!(results[0], Ok(csv!["Mike", 15]));
+    assert_eq!(
+        results[1],
+        Err(CsvError::MalformedRecord(
+            3,
+            Box::new(CsvError::RecordError)
+        ))
+    );
+    assert_eq!(results[2], Ok(csv!["Jenny", 16]));
+    assert_eq!(
+        results[3],
+        Err(CsvError::MalformedRecord(
+            5,
+            Box::new(CsvError::InvalidString)
+        ))
+    );
+}
+
+#[test]
+fn test_document_append_aligned() {
+    let mut document = Document::with_headers(&["Name", "Age", "Email"]);
+    document.add_row(csv!["Mike", 15, "kime@mail.com"]);
+
+    let mut other = Document::with_headers(&["Age", "Name"]);
+    other.add_row(csv![16, "Jenny"]);
+    document.append_aligned(&other).unwrap();
+    assert_eq!(document.shape(), (2, 3));
+    assert_eq!(
+        document.get_column::<String>("Name").unwrap(),
+        vec!["Mike", "Jenny"]
+    );
+    assert_eq!(document.get_column::<u32>("Age").unwrap(), vec![15, 16]);
+    assert_eq!(document.get_value::<String>(1, "Email").unwrap(), "");
+
+    let unknown = Document::with_headers(&["Name", "Phone"]);
+    assert_eq!(
+        document.append_aligned(&unknown),
+        Err(CsvError::InvalidColumn("Phone".to_string()))
+    );
+}
+
+#[test]
+fn test_document_apply_if() {
+    let mut document = Document::with_headers(&["Account", "Status", "Balance"]);
+    document.add_all(&[
+        csv!["A-1", "open", 120],
+        csv!["A-2", "closed", 35],
+        csv!["A-3", "closed", 7],
+    ]);
+    document.apply_if(
+        |entry| entry.get_value::<String>("Status").unwrap() == "closed",
+        |entry| entry.set_value("Balance", 0),
+    );
+    assert_eq!(
+        document.get_column::<u32>("Balance").unwrap(),
+        vec![120, 0, 0]
+    );
+}
+
+#[test]
+fn test_row_content_eq() {
+    let comma = csv!["a", "b", 3];
+    let mut semicolon = csv!["a", "b", 3];
+    semicolon.delimiter(';');
+    assert!(comma.content_eq(&semicolon));
+    assert_ne!(comma, semicolon);
+    assert!(!comma.content_eq(&csv!["a", "b"]));
+}
+
+#[test]
+fn test_reader_max_consecutive_quotes() {
+    let data = format!("Name,Note\nMike,{}\n", "\"".repeat(5000));
+    let mut reader = Reader::builder()
+        .with_reader(data.as_bytes())
+        .with_header(true)
+        .with_max_consecutive_quotes(64)
+        .build()
+        .unwrap();
+    let result = reader.read_decoded_into(|row| row.count());
+    assert_eq!(result, Err(CsvError::TooManyQuotes(2, 64)));
+
+    let mut reader = Reader::builder()
+        .with_reader("Name,Note\nMike,\"say \"\"hi\"\"\"\n".as_bytes())
+        .with_header(true)
+        .with_max_consecutive_quotes(3)
+        .build()
+        .unwrap();
+    let note = reader.read_decoded_into(|row| row.get::<String>(1));
+    assert_eq!(note.unwrap().unwrap().unwrap(), "say \"hi\"");
+}
+
+#[test]
+fn test_reader_buffer_capacity() {
+    let wide = (0..500)
+        .map(|i| i.to_string())
+        .collect::<Vec<_>>()
+        .join(",");
+    let data = format!("{wide}\n{wide}\n");
+
+    // The capacity is only a starting size, records longer than it still parse whole
+    for capacity in [0, 1, 4096] {
+        let reader = Reader::builder()
+            .with_reader(data.as_bytes())
+            .with_buffer_capacity(capacity)
+            .build()
+            .unwrap();
+        let rows: Vec<Row> = reader.entries().collect();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0], rows[1]);
+        assert_eq!(rows[0].count(), 500);
+        assert_eq!(rows[0].get::<u32>(499), Ok(499));
+    }
+}
+
+#[test]
+fn test_document_string_matrix() {
+    let mut document = Document::with_headers(&["Name", "Age"]);
+    document.add_all(&[csv!["Mike", 15], csv!["Jenny", 16]]);
+    assert_eq!(document.headers_vec(), vec!["Name", "Age"]);
+    assert_eq!(
+        document.to_string_matrix(),
+        vec![vec!["Mike", "15"], vec!["Jenny", "16"]]
+    );
+    assert!(Document::default().headers_vec().is_empty());
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn test_document_from_path_parallel() {
+    let mut contents = String::from("Id,Name,Note\r\n");
+    for i in 0..2000 {
+        contents.push_str(&format!(
+            "{i},name {i},\"multi\nline, \"\"quoted\"\" {i}\"\r\n"
+        ));
+    }
+    let path = temp_file("parallel.csv", contents.as_bytes());
+    let sequential = Document::from_path(&path).unwrap();
+    let parallel = Document::from_path_parallel(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(parallel.row_count(), 2000);
+    assert_eq!(parallel, sequential);
+}
+
+#[test]
+fn test_custom_quote_round_trip() {
+    let rows = [
+        csv!["Name", "Note"],
+        csv!["Mike", "it's, fine"],
+        csv!["Jenny", "plain"],
+    ];
+    let mut output = Vec::new();
+    {
+        let mut writer = Writer::from_writer(&mut output)
+            .with_quote('\'')
+            .with_terminator(b"\n");
+        writer.write_all(&rows).unwrap();
+    }
+    assert_eq!(
+        String::from_utf8_lossy(&output),
+        "Name,Note\nMike,'it''s, fine'\nJenny,plain\n"
+    );
+
+    let reader = Reader::builder()
+        .with_reader(output.as_slice())
+        .with_header(true)
+        .with_quote('\'')
+        .build()
+        .unwrap();
+    assert_eq!(reader.headers(), Some(rows[0].clone()));
+    assert_eq!(reader.entries().collect::<Vec<_>>(), rows[1..]);
+}
+
+#[test]
+fn test_reader_rejects_non_ascii_quote() {
+    let result = Reader::builder()
+        .with_reader("«a»,b".as_bytes())
+        .with_quote('«')
+        .build();
+    assert!(matches!(result, Err(CsvError::Generic(_))));
+}
+
+#[test]
+fn test_writer_rejects_non_ascii_quote() {
+    let mut output = Vec::new();
+    {
+        let mut writer = Writer::from_writer(&mut output)
+            .with_headers(&csv!["a", "b"])
+            .with_quote('«');
+        let rows = [csv!["«x»", "y"]];
+        assert!(matches!(writer.write(&rows[0]), Err(CsvError::Generic(_))));
+        assert!(matches!(
+            writer.write_all_aligned(&rows),
+            Err(CsvError::Generic(_))
+        ));
+    }
+    assert!(output.is_empty());
+}
+
+#[test]
+fn test_row_field() {
+    let mut row = csv!["Mike", 15];
+    row.add_bytes(b"\xff");
+    assert_eq!(row.field(0), Some(row.get::<String>(0).unwrap().as_str()));
+    assert_eq!(row.field(1), Some("15"));
+    assert_eq!(row.field(2), None);
+    assert_eq!(row.field(3), None);
+}
+
+#[test]
+fn test_writer_write_iter() {
+    let mut document = Document::with_headers(&["Name", "Age"]);
+    document.add_all(&[csv!["Mike", 15], csv!["Jenny", 16], csv!["Al", 17]]);
+
+    let mut output = Vec::new();
+    {
+        let mut writer = Writer::from_writer(&mut output).with_terminator(b"\n");
+        writer
+            .write_iter(
+                document
+                    .rows()
+                    .filter(|entry| entry.get_value::<u32>("Age").unwrap() > 15)
+                    .map(|entry| csv![entry.get_value::<String>("Name").unwrap()]),
+            )
+            .unwrap();
+    }
+    assert_eq!(String::from_utf8_lossy(&output), "Jenny\nAl\n");
+}
+
+#[test]
+fn test_document_row_index() {
+    let mut document = Document::with_headers(&["Name", "Age"]);
+    document.add_row(csv!["Mike", 15]);
+    assert!(document.contains_row(&csv!["Mike", 15]));
+
+    document.build_row_index();
+    document.add_row(csv!["Jenny", 16]);
+    assert!(document.contains_row(&csv!["Mike", 15]));
+    assert!(document.contains_row(&csv!["Jenny", 16]));
+    assert!(!document.contains_row(&csv!["Mike", 16]));
+    assert!(!document.contains_row(&csv!["Mike"]));
+}
+
+#[test]
+fn test_document_row_index_follows_changes() {
+    let mut document = Document::with_headers(&["Name", "Age"]);
+    document.add_all(&[csv!["Mike", 15], csv!["Jenny", 16], csv!["Paul", 17]]);
+    let plain = document.clone();
+    document.build_row_index();
+    assert_eq!(document, plain);
+    assert_eq!(format!("{document:?}"), format!("{plain:?}"));
+
+    document.remove_row(0);
+    assert!(!document.contains_row(&csv!["Mike", 15]));
+    assert!(document.contains_row(&csv!["Paul", 17]));
+
+    document.set_value(0, "Age", 20);
+    assert!(!document.contains_row(&csv!["Jenny", 16]));
+    assert!(document.contains_row(&csv!["Jenny", 20]));
+
+    document
+        .map_column("Name", |name| name.to_uppercase())
+        .unwrap();
+    assert!(document.contains_row(&csv!["PAUL", 17]));
+    assert!(!document.contains_row(&csv!["Paul", 17]));
+
+    for mut entry in document.rows_mut() {
+        entry.set_value("Age", 1);
+    }
+    assert!(document.contains_row(&csv!["PAUL", 1]));
+    assert!(!document.contains_row(&csv!["PAUL", 17]));
+}
+
+#[test]
+fn test_sniff_delimiter() {
+    let path = temp_file(
+        "semicolon.csv",
+        b"Name;Age;Note\nMike;15;\"a;b, c\"\nJenny;16;x\n",
+    );
+    assert_eq!(Reader::sniff_delimiter(&path), Ok(';'));
+    let reader = Reader::builder()
+        .with_reader(std::fs::File::open(&path).unwrap())
+        .with_header(true)
+        .with_auto_delimiter(true)
+        .build()
+        .unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(reader.headers(), Some(csv!["Name", "Age", "Note"]));
+    assert_eq!(reader.entries().next(), Some(csv!["Mike", 15, "a;b, c"]));
+
+    let path = temp_file("tab.csv", b"Name\tCity\nMike\tParis, FR\nJenny\tRome, IT\n");
+    assert_eq!(Reader::sniff_delimiter(&path), Ok('\t'));
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_row_try_get() {
+    let row = csv!["Mike", 15];
+    assert_eq!(row.try_get::<u32>(1), Ok(Some(15)));
+    assert_eq!(row.try_get::<u32>(2), Ok(None));
+    assert_eq!(
+        row.try_get::<u32>(0),
+        Err(CsvError::ConversionError(0, "u32".to_string()))
+    );
+}
+
+#[test]
+fn test_reader_header_map() {
+    let data: &[u8] = b" First Name ,Age\nMike,15\n";
+    let reader = Reader::builder()
+        .with_reader(data)
+        .with_header(true)
+        .with_header_map(|name| name.trim().to_lowercase().replace(' ', "_"))
+        .build()
+        .unwrap();
+    assert_eq!(reader.headers(), Some(csv!["first_name", "age"]));
+
+    let document = Document::try_from(reader).unwrap();
+    assert_eq!(
+        document.get_column::<String>("first_name").unwrap(),
+        vec!["Mike"]
+    );
+}
+
+#[test]
+fn test_document_transform_file() {
+    let input = temp_file(
+        "transform_in.csv",
+        b"Name,Age,Email,School\nMike,15,kime@mail.com,Marktown\nJenny,16,jenny@mail.com,Marktown\nAl,17,al@mail.com,Hilltop\n",
+    );
+    let output = temp_file("transform_out.csv", b"");
+    Document::transform_file(&input, &output, &["Email", "Name"], |entry| {
+        entry.get_value::<u32>("Age").unwrap() >= 16
+    })
+    .unwrap();
+
+    let document = Document::from_path(&output).unwrap();
+    std::fs::remove_file(&input).unwrap();
+    std::fs::remove_file(&output).unwrap();
+    assert_eq!(document.headers_vec(), vec!["Email", "Name"]);
+    assert_eq!(
+        document.to_string_matrix(),
+        vec![vec!["jenny@mail.com", "Jenny"], vec!["al@mail.com", "Al"]]
+    );
+}
+
+#[test]
+fn test_row_push() {
+    let mut row = Row::new();
+    assert_eq!(row.push("Mike"), 0);
+    assert_eq!(row.push(15), 1);
+    assert_eq!(row.push(1.5), 2);
+    assert_eq!(row, csv!["Mike", 15, 1.5]);
+}
+
+#[test]
+fn test_document_columns_as() {
+    let mut document = Document::with_headers(&["Name", "Age", "Grade"]);
+    document.add_all(&[csv!["Mike", 15, 9], csv!["Jenny", 16, 10]]);
+    assert_eq!(
+        document.columns_as::<u32>(&["Age", "Grade"]).unwrap(),
+        vec![vec![15, 16], vec![9, 10]]
+    );
+
+    document.add_row(csv!["Al", 17, "n/a"]);
+    assert_eq!(
+        document.columns_as::<u32>(&["Age", "Grade"]),
+        Err(CsvError::ColumnConversionError(
+            "Grade".to_string(),
+            2,
+            "u32".to_string()
+        ))
+    );
+    assert_eq!(
+        document.columns_as::<u32>(&["Height"]),
+        Err(CsvError::InvalidColumn("Height".to_string()))
+    );
+}
+
+#[test]
+fn test_reader_mid_field_quotes_are_literal() {
+    let reader = Reader::builder()
+        .with_reader("a\"b\"c,\"a\"b,a\"b\n\"x\"\"y\",\"\",z\n".as_bytes())
+        .build()
+        .unwrap();
+    let rows: Vec<Row> = reader.entries().collect();
+    assert_eq!(rows[0], csv!["a\"b\"c", "a\"b", "a\"b"]);
+    assert_eq!(rows[1], csv!["x\"y", "", "z"]);
+    assert_eq!(rows.len(), 2);
+
+    let reader = Reader::builder()
+        .with_reader("a,\"\"".as_bytes())
+        .build()
+        .unwrap();
+    assert_eq!(reader.entries().next(), Some(csv!["a", ""]));
+}
+
+#[test]
+fn test_reader_unterminated_quote() {
+    let data = "a,b\nc,\"open\nmore\n";
+    let reader = Reader::builder()
+        .with_reader(data.as_bytes())
+        .build()
+        .unwrap();
+    let rows: Vec<Row> = reader.entries().collect();
+    assert_eq!(rows, vec![csv!["a", "b"], csv!["c", "open\nmore\n"]]);
+
+    let mut reader = Reader::builder()
+        .with_reader(data.as_bytes())
+        .with_strict_quotes(true)
+        .build()
+        .unwrap();
+    assert_eq!(reader.read_decoded_into(|row| row.count()), Ok(Some(2)));
+    assert_eq!(
+        reader.read_decoded_into(|row| row.count()),
+        Err(CsvError::UnterminatedQuote("open\nmore\n".to_string()))
+    );
+}
+
+#[test]
+fn test_reader_trailing_empty_field() {
+    for data in ["a,b,\n", "a,b,", "a,b,\r\n", "x,y,z\na,b,"] {
+        let reader = Reader::builder()
+            .with_reader(data.as_bytes())
+            .build()
+            .unwrap();
+        let last = reader.entries().last().unwrap();
+        assert_eq!(last.count(), 3, "{data:?}");
+        assert_eq!(last.field(2), Some(""));
+    }
+}
+
+#[test]
+fn test_reader_skip_empty() {
+    let data = "Name,Age\n\nMike,15\n\r\n\nJenny,16\n";
+    let reader = Reader::builder()
+        .with_reader(data.as_bytes())
+        .with_header(true)
+        .with_skip_empty(true)
+        .build()
+        .unwrap();
+    assert_eq!(
+        reader.entries().collect::<Vec<_>>(),
+        vec![csv!["Mike", 15], csv!["Jenny", 16]]
+    );
+
+    let reader = Reader::builder()
+        .with_reader(data.as_bytes())
+        .with_header(true)
+        .build()
+        .unwrap();
+    assert_eq!(reader.entries().count(), 5);
+}
+
+#[test]
+fn test_writer_write_counted() {
+    let mut output = Vec::new();
+    let mut total = 0;
+    {
+        let mut writer = Writer::from_writer(&mut output).with_headers(&csv!["Name", "Note"]);
+        for row in [
+            csv!["Mike", "a,b"],
+            csv!["Jenny", "say \"hi\""],
+            csv!["Al", ""],
+        ] {
+            total += writer.write_counted(&row).unwrap();
+        }
+    }
+    assert_eq!(total, output.len());
+    assert_eq!(
+        total,
+        "Name,Note\r\nMike,\"a,b\"\r\nJenny,\"say \"\"hi\"\"\"\r\nAl,\r\n".len()
+    );
+}
+
+#[test]
+fn test_document_enumerate_rows() {
+    let mut document = Document::with_headers(&["Name", "Age"]);
+    document.add_all(&[csv!["Mike", 15], csv!["Jenny", 16], csv!["Al", 17]]);
+    let mut expected = 0;
+    for (index, entry) in document.enumerate_rows() {
+        assert_eq!(index, expected);
+        assert_eq!(
+            entry.get_value::<String>("Name").unwrap(),
+            document.get_value::<String>(index, "Name").unwrap()
+        );
+        expected += 1;
+    }
+    assert_eq!(expected, 3);
+}
+
+#[test]
+fn test_document_set_column() {
+    let mut document = Document::with_headers(&["Account", "Status"]);
+    document.add_all(&[csv!["A-1", ""], csv!["A-2", ""], csv!["A-3", ""]]);
+    document
+        .set_column("Status", &["open", "closed", "open"])
+        .unwrap();
+    assert_eq!(
+        document.get_column::<String>("Status").unwrap(),
+        vec!["open", "closed", "open"]
+    );
+
+    assert!(matches!(
+        document.set_column("Status", &["open"]),
+        Err(CsvError::Generic(_))
+    ));
+    assert_eq!(
+        document.set_column("Balance", &[0, 0, 0]),
+        Err(CsvError::InvalidColumn("Balance".to_string()))
+    );
+}
+
+struct Pupil {
+    name: String,
+    age: u32,
+}
+
+impl TryFrom<&Row> for Pupil {
+    type Error = CsvError;
+    fn try_from(row: &Row) -> Result<Self, Self::Error> {
+        Ok(Pupil {
+            name: row.get(0)?,
+            age: row.get(1)?,
+        })
+    }
+}
+
+#[test]
+fn test_reader_entries_from_row() {
+    let path = temp_file("pupils.csv", b"Name,Age\nMike,15\nJenny,abc\nAl,17\n");
+    let reader = Reader::from_path(&path).unwrap();
+    let pupils: Vec<Result<Pupil, CsvError>> = reader.entries_from_row().collect();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(pupils.len(), 3);
+    let mike = pupils[0].as_ref().unwrap();
+    assert_eq!((mike.name.as_str(), mike.age), ("Mike", 15));
+    assert!(pupils[1].is_err());
+    assert_eq!(pupils[2].as_ref().unwrap().age, 17);
+}
+
+#[test]
+fn test_reader_try_headers() {
+    let data: &[u8] = b"Name,Age\nMike,15\n";
+    let reader = Reader::builder()
+        .with_reader(data)
+        .with_header(true)
+        .build()
+        .unwrap();
+    assert_eq!(reader.try_headers(), Ok(&csv!["Name", "Age"]));
+
+    let reader = Reader::builder().with_reader(data).build().unwrap();
+    assert!(matches!(reader.try_headers(), Err(CsvError::Generic(_))));
+}
+
+#[test]
+fn test_document_filter_map_rows() {
+    let mut document = Document::with_headers(&["Name", "Age"]);
+    document.add_all(&[csv!["Mike", 15], csv!["Jenny", 16], csv!["Al", 17]]);
+    let names: Vec<String> = document.filter_map_rows(|entry| {
+        let age = entry.get_value::<u32>("Age").ok()?;
+        if age > 15 {
+            entry.get_value("Name").ok()
+        } else {
+            None
+        }
+    });
+    assert_eq!(names, vec!["Jenny", "Al"]);
+}
+
+#[test]
+fn test_writer_with_capacity() {
+    let rows: Vec<Row> = (0..1000).map(|i| csv![i, "name", i * 2]).collect();
+    let mut large = Vec::new();
+    {
+        let mut writer = Writer::with_capacity(&mut large, 1 << 20);
+        assert_eq!(writer.capacity(), 1 << 20);
+        writer.write_all(&rows).unwrap();
+    }
+    let mut default = Vec::new();
+    {
+        let mut writer = Writer::from_writer(&mut default);
+        assert_eq!(writer.capacity(), 8 * 1024);
+        writer.write_all(&rows).unwrap();
+    }
+    assert_eq!(large, default);
+}
+
+#[test]
+fn test_str_delimiter_round_trip() {
+    let rows = [
+        csv!["Name", "Ratio"],
+        csv!["Mike", "1:2"],
+        csv!["Jenny", "a::b"],
+        csv!["Al", ""],
+    ];
+    let path = temp_file("double_colon.csv", b"");
+    {
+        let mut writer = Writer::from_path(&path)
+            .unwrap()
+            .with_str_delimiter("::")
+            .with_terminator(b"\n");
+        writer.write_all(&rows).unwrap();
+    }
+    let written = std::fs::read_to_string(&path).unwrap();
+    assert_eq!(written, "Name::Ratio\nMike::1:2\nJenny::\"a::b\"\nAl::\n");
+
+    let reader = Reader::builder()
+        .with_reader(std::fs::File::open(&path).unwrap())
+        .with_header(true)
+        .with_str_delimiter("::")
+        .build()
+        .unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(reader.headers(), Some(rows[0].clone()));
+    assert_eq!(reader.entries().collect::<Vec<_>>(), rows[1..]);
+}
+
+#[test]
+fn test_row_accessors() {
+    let mut row = csv!["ab", "", "cde"];
+    assert_eq!(row.delim(), ',');
+    row.delimiter(';');
+    assert_eq!(row.delim(), ';');
+    assert_eq!(row.ranges(), &[(0, 2), (2, 2), (2, 5)]);
+    let (start, end) = row.ranges()[2];
+    assert_eq!(&row.as_bytes()[start..end], b"cde");
+}
+
+#[test]
+fn test_csv_macro_delimiter() {
+    let row = csv![delim = ';'; "a", "b", 3];
+    assert_eq!(row.delim(), ';');
+    assert_eq!(row.to_string(), "a;b;3");
+    assert!(row.content_eq(&csv!["a", "b", 3]));
+    assert_eq!(csv!["a", "b"].delim(), ',');
+}
+
+#[test]
+fn test_reader_chunks() {
+    let mut data = String::from("Id,Name\n");
+    for i in 0..7 {
+        data.push_str(&format!("{i},name {i}\n"));
+    }
+    let reader = Reader::from_str(&data).unwrap();
+    let documents: Vec<Document> = reader.chunks(3).collect();
+    assert_eq!(
+        documents
+            .iter()
+            .map(Document::row_count)
+            .collect::<Vec<_>>(),
+        vec![3, 3, 1]
+    );
+    assert_eq!(documents[1].get_column::<u32>("Id").unwrap(), vec![3, 4, 5]);
+    assert_eq!(
+        documents[2].get_value::<String>(0, "Name").unwrap(),
+        "name 6"
+    );
+}
+
+#[test]
+fn test_document_map_column() {
+    let mut document = Document::with_headers(&["Code", "Amount"]);
+    document.add_all(&[csv!["usd", 10], csv!["eur", 20], csv!["gbp", 30]]);
+    document
+        .map_column("Code", |code| code.to_uppercase())
+        .unwrap();
+    assert_eq!(
+        document.get_column::<String>("Code").unwrap(),
+        vec!["USD", "EUR", "GBP"]
+    );
+    assert_eq!(
+        document.get_column::<u32>("Amount").unwrap(),
+        vec![10, 20, 30]
+    );
+    assert_eq!(
+        document.map_column("Currency", str::to_string),
+        Err(CsvError::InvalidColumn("Currency".to_string()))
+    );
+}
+
+#[test]
+fn test_row_get_checked() {
+    let row = csv!["abc", 300, 200];
+    assert_eq!(row.get_checked::<u8>(2), Ok(200));
+    assert_eq!(
+        row.get_checked::<u8>(0),
+        Err(CsvError::DetailedConversionError(
+            0,
+            "u8".to_string(),
+            "invalid digit found in string".to_string()
+        ))
+    );
+    assert_eq!(
+        row.get_checked::<u8>(1),
+        Err(CsvError::DetailedConversionError(
+            1,
+            "u8".to_string(),
+            "number too large to fit in target type".to_string()
+        ))
+    );
+    assert_eq!(row.get_checked::<u8>(3), Err(CsvError::NotAField(3)));
+}
+
+#[test]
+fn test_row_get_opt() {
+    let row = csv!["", 42, "n/a"];
+    assert_eq!(row.get_opt::<i32>(0), Ok(None));
+    assert_eq!(row.get_opt::<i32>(1), Ok(Some(42)));
+    assert_eq!(
+        row.get_opt::<i32>(2),
+        Err(CsvError::ConversionError(2, "i32".to_string()))
+    );
+    assert_eq!(row.get_opt::<i32>(3), Err(CsvError::NotAField(3)));
+}
+
+#[test]
+fn test_document_from_rows() {
+    let data = vec![
+        vec!["Ann".to_string(), "31".to_string(), "Paris".to_string()],
+        vec!["Bob".to_string(), "27".to_string(), "Rome".to_string()],
+    ];
+    let doc = Document::from_rows(&["Name", "Age", "City"], data).unwrap();
+    assert_eq!(doc.shape(), (2, 3));
+    assert_eq!(doc.get_value::<String>(0, "Name"), Ok("Ann".to_string()));
+    assert_eq!(doc.get_value::<i32>(1, "Age"), Ok(27));
+    assert_eq!(doc.get_value::<String>(1, "City"), Ok("Rome".to_string()));
+
+    let ragged = vec![vec!["Ann".to_string()]];
+    assert_eq!(
+        Document::from_rows(&["Name", "Age"], ragged),
+        Err(CsvError::RecordError)
+    );
+}
+
+#[test]
+fn test_writer_write_fixed() {
+    let mut buffer = Vec::new();
+    {
+        let mut writer = Writer::from_writer(&mut buffer).with_terminator(b"\n");
+        writer
+            .write_fixed(&csv!["ab", "12345", "Zürich"], &[4, 3, 6])
+            .unwrap();
+        writer.write_fixed(&csv!["a,b", 7, ""], &[4, 3, 2]).unwrap();
+    }
+    assert_eq!(
+        String::from_utf8(buffer).unwrap(),
+        "  ab123Zürich\n a,b  7  \n"
+    );
+}
+
+#[test]
+fn test_reader_entries_with_headers() {
+    let reader = Reader::from_str("Name,Age\nAnn,31\nBob,27").unwrap();
+    let mut ages = 0;
+    for entry in reader.entries_with_headers() {
+        let name: String = entry.get("Name").unwrap();
+        assert!(name == "Ann" || name == "Bob");
+        ages += entry.get::<u32>("Age").unwrap();
+        assert_eq!(
+            entry.get::<u32>("Email"),
+            Err(CsvError::InvalidColumn("Email".to_string()))
+        );
+    }
+    assert_eq!(ages, 58);
+}
+
+#[test]
+fn test_reader_max_record_size() {
+    let mut data = String::from("Name,Notes\nAnn,ok\nBob,\"");
+    for _ in 0..1000 {
+        data.push_str("never closed\n");
+    }
+    let build = || {
+        Reader::builder()
+            .with_reader(data.as_bytes())
+            .with_header(true)
+            .with_max_record_size(64)
+            .build()
+            .unwrap()
+    };
+    let too_large = CsvError::RecordTooLarge(3, 64);
+
+    let (rows, errors) = build().validate();
+    assert_eq!(rows, vec![csv!["Ann", "ok"]]);
+    assert_eq!(errors[0], (3, CsvError::RecordTooLarge(3, 64)));
+    // Reading resumes after the rejected lines, each left over line being a one field record
+    assert_eq!(errors.len(), 996);
+    assert!(errors[1..]
+        .iter()
+        .all(|(_, error)| *error == CsvError::RecordError));
+
+    let results: Vec<_> = build().entries().resync().collect();
+    assert_eq!(
+        results,
+        vec![Ok(csv!["Ann", "ok"]), Err(CsvError::RecordTooLarge(3, 64))]
+    );
+
+    let mut output = Writer::from_writer(Vec::new());
+    assert_eq!(
+        tee(build(), &mut output),
+        Err(CsvError::RecordTooLarge(3, 64))
+    );
+
+    let mut reader = build();
+    assert_eq!(reader.read_decoded_into(|row| row.count()), Ok(Some(2)));
+    assert_eq!(reader.read_decoded_into(|row| row.count()), Err(too_large));
+
+    // The header is parsed while building, so the error surfaces there
+    let oversized = Reader::builder()
+        .with_reader(&data.as_bytes()[22..])
+        .with_header(true)
+        .with_max_record_size(64)
+        .build();
+    assert_eq!(oversized.err(), Some(CsvError::RecordTooLarge(1, 64)));
+}
+
+#[test]
+fn test_writer_quotes_line_breaks() {
+    let mut buffer = Vec::new();
+    {
+        let mut writer = Writer::from_writer(&mut buffer);
+        writer.write(&csv!["line1\nline2", "end"]).unwrap();
+        writer.write(&csv!["bare\rcr", "end"]).unwrap();
+    }
+    assert_eq!(
+        String::from_utf8(buffer.clone()).unwrap(),
+        "\"line1\nline2\",end\r\n\"bare\rcr\",end\r\n"
+    );
+
+    let reader = Reader::builder()
+        .with_reader(buffer.as_slice())
+        .build()
+        .unwrap();
+    let rows: Vec<Row> = reader.entries().collect();
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[0].count(), 2);
+    assert_eq!(rows[0].get::<String>(0), Ok("line1\nline2".to_string()));
+}
+
+#[test]
+fn test_document_rows_as_tuples() {
+    let mut doc = Document::with_headers(&["Name", "Age", "City"]);
+    doc.add_row(csv!["Ann", 31, "Paris"]);
+    doc.add_row(csv!["Bob", 27, "Rome"]);
+
+    let pairs = doc.rows_as2::<String, u32>("Name", "Age").unwrap();
+    assert_eq!(
+        pairs,
+        vec![("Ann".to_string(), 31), ("Bob".to_string(), 27)]
+    );
+
+    let triples = doc
+        .rows_as3::<u32, String, String>("Age", "City", "Name")
+        .unwrap();
+    assert_eq!(triples[1], (27, "Rome".to_string(), "Bob".to_string()));
+
+    assert_eq!(
+        doc.rows_as2::<String, u32>("Name", "Email"),
+        Err(CsvError::InvalidColumn("Email".to_string()))
+    );
+    assert!(doc.rows_as2::<u32, u32>("Name", "Age").is_err());
+}
+
+#[test]
+fn test_reader_skip_lines() {
+    let data = "Exported by \"tool, v2\nat 2024-01-01\nName,Age\nAnn,31\n";
+    let reader = Reader::builder()
+        .with_reader(data.as_bytes())
+        .with_skip_lines(2)
+        .with_header(true)
+        .build()
+        .unwrap();
+    assert_eq!(reader.headers(), Some(csv!["Name", "Age"]));
+    let rows: Vec<Row> = reader.entries().collect();
+    assert_eq!(rows, vec![csv!["Ann", 31]]);
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn test_document_rows_decoded_par() {
+    let mut doc = Document::with_headers(&["Name", "Age"]);
+    for i in 0..5000 {
+        doc.add_row(csv![format!("Student {i}"), i]);
+    }
+
+    let sequential: Result<Vec<Student>, CsvError> = doc.rows().map(Student::try_from).collect();
+    let parallel = doc.rows_decoded_par::<Student>();
+    assert_eq!(parallel, sequential);
+    assert_eq!(parallel.unwrap().len(), 5000);
+
+    doc.set_value(4321, "Age", "unknown");
+    assert!(doc.rows_decoded_par::<Student>().is_err());
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn test_document_rows_decoded_par_reports_first_failure() {
+    let mut doc = Document::with_headers(&["Name", "Age"]);
+    for i in 0..5000 {
+        if i == 4321 {
+            // Fails on the missing "Age" field rather than on parsing it
+            doc.add_row(csv![format!("Student {i}")]);
+        } else {
+            doc.add_row(csv![format!("Student {i}"), i]);
+        }
+    }
+    doc.set_value(1234, "Age", "unknown");
+
+    let first_failure = Student::try_from(doc.rows().nth(1234).unwrap()).unwrap_err();
+    let last_failure = Student::try_from(doc.rows().nth(4321).unwrap()).unwrap_err();
+    assert_ne!(first_failure, last_failure);
+    for _ in 0..10 {
+        assert_eq!(
+            doc.rows_decoded_par::<Student>().unwrap_err(),
+            first_failure
+        );
+    }
+}
+
+#[test]
+fn test_row_get_float_or() {
+    let row = csv!["1.5", "", "N/A", "-", "abc"];
+    let sentinels = ["N/A", "-"];
+    let values: Vec<f64> = (0..6)
+        .map(|index| row.get_float_or(index, f64::NAN, &sentinels))
+        .collect();
+    assert_eq!(values[0], 1.5);
+    assert!(values[1..].iter().all(|value| value.is_nan()));
+    assert_eq!(row.get_float_or(1, 0.0, &sentinels), 0.0);
+}
+
+#[test]
+fn test_reader_into_inner() {
+    use std::io::Read;
+
+    let data = "Name,Age\nAnn,31\nBob,27\n---\nchecksum: 42\n";
+    let mut reader = Reader::builder()
+        .with_reader(data.as_bytes())
+        .with_header(true)
+        .build()
+        .unwrap();
+    let mut names = Vec::new();
+    for _ in 0..2 {
+        names.push(
+            reader
+                .read_decoded_into(|row| row.get::<String>(0))
+                .unwrap()
+                .unwrap()
+                .unwrap(),
+        );
+    }
+    assert_eq!(names, vec!["Ann", "Bob"]);
+
+    let mut rest = String::new();
+    reader.into_inner().read_to_string(&mut rest).unwrap();
+    assert_eq!(rest, "---\nchecksum: 42\n");
+}
+
+#[test]
+fn test_reader_count_records() {
+    let path = temp_file(
+        "count.csv",
+        b"Name,Notes\r\nAnn,\"first line\r\nsecond line\"\r\nBob,\"said \"\"hi\"\"\n, bye\"\n\nCid,plain",
+    );
+    let counted = Reader::count_records(&path).unwrap();
+    let parsed = Reader::from_path(&path).unwrap().entries().count();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(counted, 4);
+    assert_eq!(counted, parsed);
+}
+
+#[test]
+fn test_document_swap_columns() {
+    let mut doc = Document::with_headers(&["Name", "Age", "City"]);
+    doc.add_row(csv!["Ann", 31, "Paris"]);
+    doc.add_row(csv!["Bob", 27, "Rome"]);
+
+    doc.swap_columns("Name", "City").unwrap();
+    assert_eq!(doc.get_headers_row(), csv!["City", "Age", "Name"]);
+    assert_eq!(doc.to_string_matrix()[1], vec!["Rome", "27", "Bob"]);
+    assert_eq!(doc.get_value::<String>(0, "Name"), Ok("Ann".to_string()));
+    assert_eq!(
+        doc.swap_columns("Name", "Email"),
+        Err(CsvError::InvalidColumn("Email".to_string()))
+    );
+}
+
+#[test]
+fn test_document_reorder_columns() {
+    let mut doc = Document::with_headers(&["Name", "Age", "City"]);
+    doc.add_row(csv!["Ann", 31, "Paris"]);
+    doc.add_row(csv!["Bob", 27, "Rome"]);
+
+    doc.reorder_columns(&["Age", "City", "Name"]).unwrap();
+    assert_eq!(doc.get_headers_row(), csv!["Age", "City", "Name"]);
+    assert_eq!(doc.get_column::<u32>("Age").unwrap(), vec![31, 27]);
+    assert_eq!(doc.get_value_at::<String>(0, 1), Ok("Paris".to_string()));
+    assert_eq!(doc.get_value::<String>(1, "Name"), Ok("Bob".to_string()));
+
+    assert!(doc.reorder_columns(&["Age", "Name"]).is_err());
+    assert!(doc.reorder_columns(&["Age", "Age", "Name"]).is_err());
+    assert_eq!(doc.get_headers_row(), csv!["Age", "City", "Name"]);
+}
+
+#[test]
+fn test_row_builder() {
+    let header = csv!["Name", "Age", "City"];
+    let row = RowBuilder::new(&header)
+        .with_required(&["Name", "Age"])
+        .set("City", "Paris")
+        .set("Age", 31)
+        .set("Name", "Ann")
+        .build()
+        .unwrap();
+    assert_eq!(row, csv!["Ann", 31, "Paris"]);
+
+    let partial = RowBuilder::new(&header).set("Age", 27).build().unwrap();
+    assert_eq!(partial, csv!["", 27, ""]);
+
+    let missing = RowBuilder::new(&header)
+        .with_required(&["Name"])
+        .set("Age", 27)
+        .build();
+    assert!(matches!(missing, Err(CsvError::Generic(_))));
+
+    let unknown = RowBuilder::new(&header).set("Email", "a@b.c").build();
+    assert_eq!(unknown, Err(CsvError::InvalidColumn("Email".to_string())));
+}
+
+#[test]
+fn test_reader_record_separator() {
+    let data = "Name\u{1f}Notes\u{1e}Ann\u{1f}first\nsecond\u{1e}Bob\u{1f}\"as is\"\u{1e}";
+    let reader = Reader::builder()
+        .with_reader(data.as_bytes())
+        .with_delimiter('\u{1f}')
+        .with_record_separator('\u{1e}')
+        .with_header(true)
+        .build()
+        .unwrap();
+    assert_eq!(
+        reader.headers().unwrap().get::<String>(1),
+        Ok("Notes".to_string())
+    );
+    let rows: Vec<Row> = reader.entries().collect();
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[0].get::<String>(0), Ok("Ann".to_string()));
+    assert_eq!(rows[0].get::<String>(1), Ok("first\nsecond".to_string()));
+    assert_eq!(rows[1].get::<String>(1), Ok("as is".to_string()));
+}
+
+#[test]
+fn test_reader_record_separator_non_ascii() {
+    // '€' and '¬' share the trailing byte 0xAC of their UTF-8 encoding
+    let data = "a,b¬x€c,\"d€\"€";
+    let reader = Reader::builder()
+        .with_reader(data.as_bytes())
+        .with_record_separator('€')
+        .build()
+        .unwrap();
+    let rows: Vec<Row> = reader.entries().collect();
+    assert_eq!(rows, vec![csv!["a", "b¬x"], csv!["c", "d€"]]);
+}
+
+#[test]
+fn test_writer_write_all_aligned() {
+    let mut buffer = Vec::new();
+    {
+        let mut writer = Writer::from_writer(&mut buffer)
+            .with_terminator(b"\n")
+            .with_headers(&csv!["Name", "Age", "City"]);
+        writer
+            .write_all_aligned(&[csv!["Zoë", 7, "Paris"], csv!["Bob, Jr.", 101, "Rome"]])
+            .unwrap();
+    }
+    let output = String::from_utf8(buffer).unwrap();
+    assert_eq!(
+        output,
+        "Name      ,Age,City\nZoë       ,7  ,Paris\n\"Bob, Jr.\",101,Rome\n"
+    );
+
+    // The last column starts at the same character on every line
+    let starts: Vec<usize> = output
+        .lines()
+        .map(|line| line[..line.rfind(',').unwrap()].chars().count())
+        .collect();
+    assert_eq!(starts, vec![14, 14, 14]);
+}
+
+#[test]
+fn test_doc_entry_values() {
+    let headers: Vec<String> = (0..32).map(|i| format!("Column {i}")).collect();
+    let header_refs: Vec<&str> = headers.iter().map(String::as_str).collect();
+    let mut doc = Document::with_headers(&header_refs);
+    let values: Vec<String> = (0..32).map(|i| format!("value {i}")).collect();
+    doc.add_row(Row::from(values.clone()));
+    doc.add_row(csv!["short"]);
+    let mut invalid = Row::new();
+    invalid.add_bytes(b"bad\xff");
+    doc.add_row(invalid);
+
+    let mut rows = doc.rows();
+    assert_eq!(rows.next().unwrap().values(), values);
+    let short = rows.next().unwrap().values();
+    assert_eq!(short.len(), 32);
+    assert_eq!(short[0], "short");
+    assert!(short[1..].iter().all(String::is_empty));
+    assert_eq!(rows.next().unwrap().values()[0], "bad\u{fffd}");
+}
+
+#[test]
+fn test_doc_entry_debug_in_header_order() {
+    let mut doc = Document::with_headers(&["Name", "Age", "City"]);
+    doc.add_row(csv!["Ann", 31, "Paris"]);
+
+    let expected = r#"DocEntry { Name: "Ann", Age: "31", City: "Paris" }"#;
+    for _ in 0..10 {
+        assert_eq!(format!("{:?}", doc.rows().next().unwrap()), expected);
+    }
+    assert_eq!(
+        format!("{:?}", doc.rows_mut().next().unwrap()),
+        r#"DocEntryMut { Name: "Ann", Age: "31", City: "Paris" }"#
+    );
+}
+
+#[test]
+fn test_field_is_empty() {
+    assert!(Field::from("").is_empty());
+    assert!(!Field::from(" ").is_empty());
+}
+
+#[test]
+fn test_row_is_empty_and_blank() {
+    let empty = Row::new();
+    assert!(empty.is_empty());
+    assert!(empty.is_blank());
+
+    let blank = csv!["", "", ""];
+    assert!(!blank.is_empty());
+    assert!(blank.is_blank());
+
+    let populated = csv!["", "value", ""];
+    assert!(!populated.is_empty());
+    assert!(!populated.is_blank());
+}
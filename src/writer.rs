@@ -33,11 +33,18 @@ use std::{
 
 use crate::*;
 
+/// Size of the write buffer used by [`Writer::from_writer`] and [`Writer::from_path`], same as [`BufWriter::new`].
+const DEFAULT_BUFFER_CAPACITY: usize = 8 * 1024;
+
 /// A CSV Writer implementation. Write to files or standard output.
 pub struct Writer<R: io::Write> {
     writer: BufWriter<R>,
-    delimiter: Option<char>,
-    // row: Vec<u8>,
+    delimiter: Option<Vec<u8>>,
+    terminator: Vec<u8>,
+    null_values: Vec<Vec<u8>>,
+    pending_header: Option<Row>,
+    formula_guard: Option<char>,
+    quote: char,
 }
 
 impl Writer<std::fs::File> {
@@ -54,11 +61,10 @@ impl Writer<std::fs::File> {
     /// # Error
     /// If the underlying file behind path is not accessible for any reason.
     pub fn from_path(path: impl AsRef<Path>) -> Result<Self> {
-        let writer = BufWriter::new(std::fs::File::create(path).map_err(|_| CsvError::FileError)?);
-        Ok(Self {
-            writer,
-            delimiter: None,
-        })
+        Ok(Self::with_capacity(
+            std::fs::File::create(path)?,
+            DEFAULT_BUFFER_CAPACITY,
+        ))
     }
 }
 
@@ -68,65 +74,242 @@ impl<R: io::Write + Sized> Writer<R> {
     /// # Arguments:
     /// `writer` std::io::Write implementation to write to
     pub fn from_writer(writer: R) -> Self {
+        Self::with_capacity(writer, DEFAULT_BUFFER_CAPACITY)
+    }
+
+    /// Initialize a CSV Writer from a std::io::Write implementation with a custom buffer size.
+    ///
+    /// A larger buffer flushes less often, which helps on large exports.
+    ///
+    /// # Arguments:
+    /// `writer` std::io::Write implementation to write to
+    /// `capacity` size in bytes of the write buffer, defaults to 8 KiB with [`Writer::from_writer`].
+    pub fn with_capacity(writer: R, capacity: usize) -> Self {
         Self {
-            writer: BufWriter::new(writer),
+            writer: BufWriter::with_capacity(capacity, writer),
             delimiter: None,
-            // row: Vec::new(),
+            terminator: vec![CR, LF],
+            null_values: Vec::new(),
+            pending_header: None,
+            formula_guard: None,
+            quote: QUOTE as char,
         }
     }
 
+    /// Retrieves the size in bytes of the write buffer.
+    pub fn capacity(&self) -> usize {
+        self.writer.capacity()
+    }
+
     /// Set a delimiter for a writer
     /// # Arguments:
     /// `delim` delimiter for CSV rows being written.
     pub fn with_delimiter(mut self, delim: char) -> Self {
-        self.delimiter = Some(delim);
+        self.delimiter = Some(delim.to_string().into_bytes());
+        self
+    }
+
+    /// Set a delimiter made of several characters, such as `::` or `||`, for a writer.
+    ///
+    /// An empty delimiter is ignored.
+    /// # Arguments:
+    /// `delim` string delimiter written between fields.
+    pub fn with_str_delimiter(mut self, delim: &str) -> Self {
+        if !delim.is_empty() {
+            self.delimiter = Some(delim.as_bytes().to_vec());
+        }
+        self
+    }
+
+    /// Set the quote character used to escape fields. Defaults to `"`.
+    ///
+    /// Fields containing the quote character or the delimiter are wrapped in it,
+    /// and the quote character is doubled inside them.
+    /// # Arguments:
+    /// `quote` ASCII quote character used for escaping. Writing fails when it is not ASCII.
+    pub fn with_quote(mut self, quote: char) -> Self {
+        self.quote = quote;
+        self
+    }
+
+    /// Set the line terminator written after each record.
+    ///
+    /// Defaults to `\r\n`. Use `b"\n"` for Unix style line endings.
+    /// # Arguments:
+    /// `terminator` bytes written at the end of every CSV row.
+    pub fn with_terminator(mut self, terminator: &[u8]) -> Self {
+        self.terminator = terminator.to_vec();
+        self
+    }
+
+    /// Set the values that consumers of the output treat as null.
+    ///
+    /// Any field exactly matching one of these sentinels is written quoted,
+    /// so a literal `NA` in the data stays distinguishable from a missing value.
+    /// Null fields, see [`Row::add_null`], are written as the first sentinel, unquoted.
+    /// # Arguments:
+    /// `null_values` sentinel values considered null, e.g. `&["NA", "NULL"]`.
+    pub fn with_null_values(mut self, null_values: &[&str]) -> Self {
+        self.null_values = null_values
+            .iter()
+            .map(|value| value.as_bytes().to_vec())
+            .collect();
+        self
+    }
+
+    /// Guard against CSV injection when the output is opened in a spreadsheet.
+    ///
+    /// When enabled, fields starting with `=`, `+`, `-` or `@` are prefixed with a single
+    /// quote so they are treated as text instead of formulas.
+    /// See [`Writer::with_formula_guard_prefix`] to use another prefix.
+    ///
+    /// # Arguments:
+    /// `enabled` boolean whether formula-like fields are neutralized.
+    pub fn with_formula_guard(mut self, enabled: bool) -> Self {
+        self.formula_guard = if enabled { Some('\'') } else { None };
+        self
+    }
+
+    /// Enable the formula guard using a custom prefix, such as a tab.
+    ///
+    /// # Arguments:
+    /// `prefix` character written before fields that look like formulas.
+    pub fn with_formula_guard_prefix(mut self, prefix: char) -> Self {
+        self.formula_guard = Some(prefix);
+        self
+    }
+
+    /// Set a header to be written once, right before the first row.
+    ///
+    /// # Arguments:
+    /// `header` header row emitted ahead of the first call to `write` or `write_all`.
+    pub fn with_headers(mut self, header: &Row) -> Self {
+        self.pending_header = Some(header.clone());
         self
     }
 
     /// Writes a single CSV [`row`]
     ///
+    /// If a header was set through [`Writer::with_headers`] and not written yet, it is written first.
+    ///
     /// # Arguments:
     /// `row` CSV row to be written.
     pub fn write(&mut self, row: &Row) -> Result<()> {
-        let delimiter = match self.delimiter {
-            Some(delim) => delim as u8,
-            _ => row.delim as u8,
+        self.write_counted(row)?;
+        Ok(())
+    }
+
+    /// Writes a single CSV [`row`] like [`Writer::write`], returning the number of bytes emitted.
+    ///
+    /// The count includes the terminator, and the header when it is written by this call,
+    /// so adding up the counts gives the size of the output.
+    ///
+    /// # Arguments:
+    /// `row` CSV row to be written.
+    pub fn write_counted(&mut self, row: &Row) -> Result<usize> {
+        // Fail before the pending header is taken, so it is still written by a later call
+        self.quote_byte()?;
+        let mut written = 0;
+        if let Some(header) = self.pending_header.take() {
+            written += self.write_row(&header, &[])?;
+        }
+        Ok(written + self.write_row(row, &[])?)
+    }
+
+    /// Escapes and writes the fields of a row followed by the terminator.
+    ///
+    /// Fields are padded with spaces up to their width in `widths`, the last field is never padded.
+    ///
+    /// # Returns
+    /// The number of bytes written.
+    fn write_row(&mut self, row: &Row, widths: &[usize]) -> Result<usize> {
+        let mut buffer = [0; 4];
+        let style = FieldStyle {
+            delimiter: match &self.delimiter {
+                Some(delim) => delim.as_slice(),
+                _ => row.delim.encode_utf8(&mut buffer).as_bytes(),
+            },
+            quote: self.quote_byte()?,
+            formula_guard: self.formula_guard,
+            null_values: &self.null_values,
+        };
+        let mut out = CountingWriter {
+            inner: &mut self.writer,
+            count: 0,
+            chars: 0,
         };
 
         // Since we now write behind a buffered writer, we can write single characters without much penalty
         // May not be pretty but it helps a lot in performance
         for (index, (start, end)) in row.ranges.iter().enumerate() {
-            // To avoid slow allocation and string formatting, we escape fields manually
-            let field = &row.inner[*start..*end];
-
-            if field.contains(&QUOTE) {
-                // When we have quotes, we escape each quote and put quotes around the field itself
-                self.writer.write_all(&[QUOTE])?;
-                for byte in field {
-                    if byte == &QUOTE {
-                        // escape the quote!
-                        self.writer.write_all(&[*byte, QUOTE])?;
-                    } else {
-                        self.writer.write_all(&[*byte])?;
-                    }
-                }
-                self.writer.write_all(&[QUOTE])?;
-            } else if field.contains(&delimiter) {
-                // If the delimiter is part of the field, then let's escape the field
-                self.writer.write_all(&[QUOTE])?;
-                self.writer.write_all(field)?;
-                self.writer.write_all(&[QUOTE])?;
+            let chars = out.chars;
+            if row.is_null(index) {
+                style.write_null(&mut out)?;
             } else {
-                self.writer.write_all(field)?;
+                style.write_field(&mut out, &row.inner[*start..*end])?;
             }
 
             if index != row.ranges.len() - 1 {
+                if let Some(width) = widths.get(index) {
+                    for _ in (out.chars - chars)..*width {
+                        out.write_all(b" ")?;
+                    }
+                }
                 // We only add the delimiter at the end of the each field except for the last
-                self.writer.write_all(&[delimiter])?;
+                out.write_all(style.delimiter)?;
             }
         }
-        self.writer.write_all(&[CR, LF])?;
+        out.write_all(&self.terminator)?;
+
+        Ok(out.count)
+    }
+
+    /// The quote character as a single byte.
+    ///
+    /// # Errors
+    /// [`CsvError::Generic`] if the quote set through [`Writer::with_quote`] is not ASCII.
+    fn quote_byte(&self) -> Result<u8> {
+        if self.quote.is_ascii() {
+            Ok(self.quote as u8)
+        } else {
+            Err(CsvError::Generic(format!(
+                "Quote character `{}` must be ASCII.",
+                self.quote
+            )))
+        }
+    }
+
+    /// Writes a single [`row`] as fixed-width columns instead of delimited CSV.
+    ///
+    /// Each field is padded with spaces on the left up to its width, and no delimiters or
+    /// quotes are written. A field longer than its width is truncated, keeping its first
+    /// `width` characters. Fields without a matching width are not written.
+    /// If a header was set through [`Writer::with_headers`] and not written yet, it is written
+    /// first using the same widths.
+    ///
+    /// # Arguments:
+    /// `row` CSV row to be written.
+    /// `widths` width in characters of each column, in order.
+    ///
+    /// # Errors
+    /// [`CsvError::InvalidString`] if a field is not valid UTF-8.
+    pub fn write_fixed(&mut self, row: &Row, widths: &[usize]) -> Result<()> {
+        if let Some(header) = self.pending_header.take() {
+            self.write_fixed_row(&header, widths)?;
+        }
+        self.write_fixed_row(row, widths)
+    }
 
+    /// Pads or truncates the fields of a row to their widths followed by the terminator.
+    fn write_fixed_row(&mut self, row: &Row, widths: &[usize]) -> Result<()> {
+        for (field, width) in row.iter().zip(widths) {
+            let value = field.try_str()?;
+            match value.char_indices().nth(*width) {
+                Some((end, _)) => self.writer.write_all(&value.as_bytes()[..end])?,
+                None => write!(self.writer, "{value:>width$}", width = *width)?,
+            }
+        }
+        self.writer.write_all(&self.terminator)?;
         Ok(())
     }
 
@@ -140,4 +323,127 @@ impl<R: io::Write + Sized> Writer<R> {
         }
         Ok(())
     }
+
+    /// Writes several [`row`]s with every column padded to a common width, so they line up visually.
+    ///
+    /// Widths are measured in characters of the escaped fields across all rows, and the header
+    /// when it is pending. Padding is written as trailing spaces before each delimiter, trimming
+    /// fields when reading the output gives back the original values.
+    ///
+    /// # Arguments
+    /// `rows`  vector of rows to be written.
+    pub fn write_all_aligned(&mut self, rows: &[Row]) -> Result<()> {
+        let quote = self.quote_byte()?;
+        let header = self.pending_header.take();
+        let mut widths: Vec<usize> = Vec::new();
+        for row in header.iter().chain(rows) {
+            let mut buffer = [0; 4];
+            let style = FieldStyle {
+                delimiter: match &self.delimiter {
+                    Some(delim) => delim.as_slice(),
+                    _ => row.delim.encode_utf8(&mut buffer).as_bytes(),
+                },
+                quote,
+                formula_guard: self.formula_guard,
+                null_values: &self.null_values,
+            };
+            for (index, field) in row.iter().enumerate() {
+                let mut out = CountingWriter {
+                    inner: io::sink(),
+                    count: 0,
+                    chars: 0,
+                };
+                if row.is_null(index) {
+                    style.write_null(&mut out)?;
+                } else {
+                    style.write_field(&mut out, field.as_bytes())?;
+                }
+                match widths.get_mut(index) {
+                    Some(width) => *width = (*width).max(out.chars),
+                    None => widths.push(out.chars),
+                }
+            }
+        }
+
+        for row in header.iter().chain(rows) {
+            self.write_row(row, &widths)?;
+        }
+        Ok(())
+    }
+
+    /// Writes every [`row`] yielded by an iterator, without collecting them first.
+    ///
+    /// # Arguments
+    /// `rows`  rows to be written, e.g. a lazily filtered and mapped reader.
+    pub fn write_iter<I>(&mut self, rows: I) -> Result<()>
+    where
+        I: IntoIterator<Item = Row>,
+    {
+        for row in rows {
+            self.write(&row)?;
+        }
+        Ok(())
+    }
+}
+
+/// How a [`Writer`] escapes each field it writes.
+struct FieldStyle<'a> {
+    delimiter: &'a [u8],
+    quote: u8,
+    formula_guard: Option<char>,
+    null_values: &'a [Vec<u8>],
+}
+
+impl FieldStyle<'_> {
+    /// Writes a single field, guarded against formulas and escaped as needed.
+    fn write_field(&self, out: &mut impl io::Write, field: &[u8]) -> io::Result<()> {
+        // To avoid slow allocation and string formatting, we escape fields manually
+        let mut field = field;
+
+        // Only allocate when a formula-like field needs its guard prefix
+        let guarded;
+        if let Some(prefix) = self.formula_guard {
+            if matches!(field.first(), Some(b'=' | b'+' | b'-' | b'@')) {
+                let mut buffer = [0; 4];
+                guarded = [prefix.encode_utf8(&mut buffer).as_bytes(), field].concat();
+                field = &guarded;
+            }
+        }
+
+        // A field that could be mistaken for a null is quoted even if it needs no escaping
+        let is_null_like = self.null_values.iter().any(|null| null == field);
+        write_escaped_field(out, field, self.delimiter, self.quote, is_null_like)
+    }
+
+    /// Writes a null field as the first null value, unquoted, or nothing without null values.
+    fn write_null(&self, out: &mut impl io::Write) -> io::Result<()> {
+        match self.null_values.first() {
+            Some(null) => out.write_all(null),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Forwards writes to another writer while counting the bytes and characters written.
+struct CountingWriter<W> {
+    inner: W,
+    count: usize,
+    chars: usize,
+}
+
+impl<W: io::Write> io::Write for CountingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.inner.write(buf)?;
+        self.count += written;
+        // Every byte except UTF-8 continuation bytes starts a new character
+        self.chars += buf[..written]
+            .iter()
+            .filter(|byte| (**byte & 0xC0) != 0x80)
+            .count();
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
 }